@@ -164,9 +164,7 @@ pub mod request {
                 Err(Error::InvalidPacket)?
             }
 
-            let mut buffer = self.buffer.into_inner();
-            self.finalizer.finalize(buffer.as_mut())?;
-            Ok(buffer)
+            self.finalizer.build(self.buffer)
         }
     }
 
@@ -284,9 +282,7 @@ pub mod response {
                 Err(Error::InvalidPacket)?
             }
 
-            let mut buffer = self.buffer.into_inner();
-            self.finalizer.finalize(buffer.as_mut())?;
-            Ok(buffer)
+            self.finalizer.build(self.buffer)
         }
     }
 
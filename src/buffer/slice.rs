@@ -21,6 +21,7 @@ use crate::error::*;
 pub struct Buffer<'a> {
 	inner: &'a mut [u8],
 
+	start:  usize,
 	offset: usize,
 	length: usize,
 	used:   usize,
@@ -32,18 +33,94 @@ impl<'a> Buffer<'a> {
 		Buffer {
 			inner: slice,
 
+			start:  0,
 			offset: 0,
 			length: 0,
 			used:   0,
 		}
 	}
+
+	/// Wrap a slice already holding data after `headroom` bytes of free space,
+	/// the data becomes the current layer.
+	///
+	/// The headroom allows the current layer to grow at the front, for instance
+	/// to push tags or encapsulation headers without moving the payload.
+	pub fn with_headroom(slice: &mut [u8], headroom: usize) -> Result<Buffer<'_>> {
+		if slice.len() < headroom {
+			Err(Error::SmallBuffer)?
+		}
+
+		let size = slice.len();
+
+		Ok(Buffer {
+			inner: slice,
+
+			start:  headroom,
+			offset: headroom,
+			length: size - headroom,
+			used:   size,
+		})
+	}
+
+	/// Free space in front of the used part of the buffer.
+	pub fn headroom(&self) -> usize {
+		self.start
+	}
+
+	/// Grow the current layer at the front by taking from the headroom,
+	/// zeroeing the new buffer area.
+	///
+	/// # Note
+	///
+	/// Only the first layer can grow at the front.
+	pub fn prepend(&mut self, size: usize) -> Result<()> {
+		if self.offset != self.start {
+			Err(Error::InvalidValue)?
+		}
+
+		if self.start < size {
+			Err(Error::SmallBuffer)?
+		}
+
+		self.start  -= size;
+		self.offset -= size;
+		self.length += size;
+
+		for byte in &mut self.inner[self.offset .. self.offset + size] {
+			*byte = 0;
+		}
+
+		Ok(())
+	}
+
+	/// Shrink the current layer at the front, giving the space back to the
+	/// headroom.
+	///
+	/// # Note
+	///
+	/// Only the first layer can shrink at the front.
+	pub fn strip(&mut self, size: usize) -> Result<()> {
+		if self.offset != self.start {
+			Err(Error::InvalidValue)?
+		}
+
+		if self.length < size {
+			Err(Error::SmallBuffer)?
+		}
+
+		self.start  += size;
+		self.offset += size;
+		self.length -= size;
+
+		Ok(())
+	}
 }
 
 impl<'a> super::Buffer for Buffer<'a> {
 	type Inner = &'a mut [u8];
 
 	fn into_inner(self) -> Self::Inner {
-		&mut self.inner[0 .. self.used]
+		&mut self.inner[self.start .. self.used]
 	}
 
 	fn next(&mut self, size: usize) -> Result<()> {
@@ -67,7 +144,6 @@ impl<'a> super::Buffer for Buffer<'a> {
 			Err(Error::SmallBuffer)?
 		}
 
-		self.offset  = self.used;
 		self.length += size;
 		self.used   += size;
 
@@ -80,9 +156,9 @@ impl<'a> super::Buffer for Buffer<'a> {
 	}
 
 	fn clear(&mut self) {
-		self.offset = 0;
+		self.offset = self.start;
 		self.length = 0;
-		self.used   = 0;
+		self.used   = self.start;
 	}
 
 	fn used(&self) -> usize {
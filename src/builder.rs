@@ -23,6 +23,14 @@ use crate::buffer::Buffer;
 pub trait Finalizer {
 	/// Run the finalizer on the given buffer.
 	fn finalize(self: Box<Self>, buffer: &mut [u8]) -> Result<()>;
+
+	/// Size of the trailer needed after a packet of the given size, if any.
+	///
+	/// Finalizers with a trailer get the whole buffer, while the other ones
+	/// get the buffer without any trailer.
+	fn trailer(&self, _size: usize) -> Option<usize> {
+		None
+	}
 }
 
 impl<F: FnOnce(&mut [u8]) -> Result<()>> Finalizer for F {
@@ -32,6 +40,23 @@ impl<F: FnOnce(&mut [u8]) -> Result<()>> Finalizer for F {
 	}
 }
 
+/// A finalizer that needs space after the packet, like padding or a frame
+/// check sequence.
+pub struct Trailer<S, F> {
+	size:      S,
+	finalizer: F,
+}
+
+impl<S: Fn(usize) -> usize, F: FnOnce(&mut [u8]) -> Result<()>> Finalizer for Trailer<S, F> {
+	fn finalize(self: Box<Self>, buffer: &mut [u8]) -> Result<()> {
+		(self.finalizer)(buffer)
+	}
+
+	fn trailer(&self, size: usize) -> Option<usize> {
+		Some((self.size)(size))
+	}
+}
+
 /// Takes care of grouping finalizers through the builder chain.
 pub struct Finalization(Vec<Box<dyn Finalizer>>);
 
@@ -55,15 +80,54 @@ impl Finalization {
 		self.0.push(Box::new(finalizer));
 	}
 
+	/// Add a new finalizer needing a trailer, the size is calculated from the
+	/// size of the whole packet.
+	pub fn trailer<S, F>(&mut self, size: S, finalizer: F)
+		where S: Fn(usize) -> usize + 'static,
+		      F: FnOnce(&mut [u8]) -> Result<()> + 'static
+	{
+		self.0.push(Box::new(Trailer { size, finalizer }));
+	}
+
 	/// Add a serie of finalizers.
 	pub fn extend<I: IntoIterator<Item = Box<dyn Finalizer>>>(&mut self, finalizers: I) {
 		self.0.extend(finalizers.into_iter());
 	}
 
 	/// Finalize a buffer.
+	///
+	/// # Note
+	///
+	/// The buffer is not grown to make room for trailers, use `build` to do so.
 	pub fn finalize(self, buffer: &mut [u8]) -> Result<()> {
+		self.run(buffer, 0)
+	}
+
+	/// Reserve the space for any trailer, then finalize the buffer.
+	pub fn build<B: Buffer>(self, mut buffer: B) -> Result<B::Inner> {
+		let size    = buffer.used();
+		let trailer = self.0.iter().filter_map(|f| f.trailer(size)).sum();
+
+		if trailer > 0 {
+			buffer.more(trailer)?;
+		}
+
+		let mut buffer = buffer.into_inner();
+		self.run(buffer.as_mut(), trailer)?;
+
+		Ok(buffer)
+	}
+
+	fn run(self, buffer: &mut [u8], trailer: usize) -> Result<()> {
+		let size = buffer.len() - trailer;
+
 		for finalizer in self.0.into_iter().rev() {
-			finalizer.finalize(buffer)?;
+			if finalizer.trailer(size).is_some() {
+				finalizer.finalize(buffer)?;
+			}
+			else {
+				finalizer.finalize(&mut buffer[.. size])?;
+			}
 		}
 
 		Ok(())
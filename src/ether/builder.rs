@@ -12,7 +12,7 @@
 //
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
-use byteorder::{BigEndian, WriteBytesExt};
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
 use hwaddr::HwAddr;
 use std::io::Cursor;
 
@@ -20,7 +20,9 @@ use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::error::*;
 use crate::ether::Packet;
+use crate::ether::packet::MAX_TAGS;
 use crate::ether::Protocol;
+use crate::ether::vlan;
 use crate::packet::{AsPacket, AsPacketMut};
 
 /// Ethernet frame builder.
@@ -30,6 +32,7 @@ pub struct Builder<B: Buffer = buffer::Dynamic> {
     finalizer: Finalization,
 
     payload: bool,
+    fcs: bool,
 }
 
 impl<B: Buffer> Build<B> for Builder<B> {
@@ -42,6 +45,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
             finalizer: Default::default(),
 
             payload: false,
+            fcs: false,
         })
     }
 
@@ -50,9 +54,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
     }
 
     fn build(self) -> Result<B::Inner> {
-        let mut buffer = self.buffer.into_inner();
-        self.finalizer.finalize(buffer.as_mut())?;
-        Ok(buffer)
+        self.finalizer.build(self.buffer)
     }
 }
 
@@ -91,7 +93,73 @@ impl<B: Buffer> Builder<B> {
 
     /// Protocol of the inner packet.
     pub fn protocol(mut self, value: Protocol) -> Result<Self> {
-        Packet::unchecked(self.buffer.data_mut()).set_protocol(value)?;
+        let data = self.buffer.data_mut();
+        let length = data.len();
+
+        Cursor::new(&mut data[length - 2..]).write_u16::<BigEndian>(value.into())?;
+
+        Ok(self)
+    }
+
+    /// Add an 802.1Q tag with the given VLAN Identifier and Priority Code
+    /// Point.
+    ///
+    /// Calling it again stacks another tag inside the previous ones, in which
+    /// case the outermost tag is marked as an 802.1ad service tag, at most two
+    /// tags can be stacked.
+    pub fn vlan(mut self, vid: u16, pcp: u8) -> Result<Self> {
+        if self.payload {
+            Err(Error::AlreadyDefined)?
+        }
+
+        if Packet::unchecked(self.buffer.data()).tags().count() >= MAX_TAGS {
+            Err(Error::AlreadyDefined)?
+        }
+
+        if vid > 0xfff || pcp > 0b111 {
+            Err(Error::InvalidValue)?
+        }
+
+        let protocol = Packet::unchecked(self.buffer.data()).inner_protocol();
+        self.buffer.more(4)?;
+
+        let data = self.buffer.data_mut();
+        let length = data.len();
+
+        if length > 18 {
+            vlan::Tag::unchecked(&mut data[12..]).set_protocol(Protocol::PBridge)?;
+        }
+
+        vlan::Tag::unchecked(&mut data[length - 6..])
+            .set_protocol(Protocol::Vlan)?
+            .set_pcp(pcp)?
+            .set_vid(vid)?;
+
+        Cursor::new(&mut data[length - 2..]).write_u16::<BigEndian>(protocol.into())?;
+
+        Ok(self)
+    }
+
+    /// Append the frame check sequence, padding the frame to the 60 bytes
+    /// minimum if needed.
+    pub fn fcs(mut self) -> Result<Self> {
+        if self.fcs {
+            Err(Error::AlreadyDefined)?
+        }
+
+        self.fcs = true;
+
+        let offset = self.buffer.offset();
+        self.finalizer.trailer(
+            move |size| (60 + offset).saturating_sub(size) + 4,
+            move |out| {
+                let length = out.len();
+                let checksum = super::checksum(&out[offset..length - 4]);
+                Cursor::new(&mut out[length - 4..]).write_u32::<LittleEndian>(checksum)?;
+
+                Ok(())
+            },
+        );
 
         Ok(self)
     }
@@ -123,10 +191,10 @@ impl<B: Buffer> Builder<B> {
 
         self.finalizer.add(move |out| {
             match out[offset + length] >> 4 {
-                4 => Cursor::new(&mut out[offset + 12..])
+                4 => Cursor::new(&mut out[offset + length - 2..])
                     .write_u16::<BigEndian>(Protocol::Ipv4.into())?,
 
-                6 => Cursor::new(&mut out[offset + 12..])
+                6 => Cursor::new(&mut out[offset + length - 2..])
                     .write_u16::<BigEndian>(Protocol::Ipv6.into())?,
 
                 _ => unreachable!(),
@@ -141,16 +209,31 @@ impl<B: Buffer> Builder<B> {
         Ok(ip)
     }
 
+    /// Build an ARP packet inside the Ethernet frame.
     pub fn arp(self) -> Result<crate::arp::Builder<B>> {
         if self.payload {
             Err(Error::AlreadyDefined)?
         }
 
-        let mut arp = crate::arp::Builder::with(self.buffer)?;
-        arp.finalizer().extend(self.finalizer);
+        let builder = self.protocol(Protocol::Arp)?;
+        let mut arp = crate::arp::Builder::with(builder.buffer)?;
+        arp.finalizer().extend(builder.finalizer);
 
         Ok(arp)
     }
+
+    /// Build an LLDP packet inside the Ethernet frame.
+    pub fn lldp(self) -> Result<crate::lldp::Builder<B>> {
+        if self.payload {
+            Err(Error::AlreadyDefined)?
+        }
+
+        let builder = self.protocol(Protocol::Lldp)?;
+        let mut lldp = crate::lldp::Builder::with(builder.buffer)?;
+        lldp.finalizer().extend(builder.finalizer);
+
+        Ok(lldp)
+    }
 }
 
 #[cfg(test)]
@@ -213,4 +296,146 @@ mod test {
         assert_eq!(udp.destination(), 9001);
         assert!(udp.is_valid(&ip::Packet::from(&ip)));
     }
+
+    #[test]
+    fn vlan() {
+        let packet = ether::Builder::default()
+            .destination("00:23:69:63:59:be".parse().unwrap())
+            .unwrap()
+            .source("e4:b3:18:26:63:a3".parse().unwrap())
+            .unwrap()
+            .vlan(10, 0)
+            .unwrap()
+            .vlan(100, 5)
+            .unwrap()
+            .ip()
+            .unwrap()
+            .v4()
+            .unwrap()
+            .ttl(64)
+            .unwrap()
+            .source("66.102.1.108".parse().unwrap())
+            .unwrap()
+            .destination("192.168.0.79".parse().unwrap())
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ether = ether::Packet::new(packet).unwrap();
+        assert_eq!(ether.protocol(), ether::Protocol::PBridge);
+        assert_eq!(ether.inner_protocol(), ether::Protocol::Ipv4);
+
+        let tags = ether.tags().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].vid(), 10);
+        assert_eq!(tags[0].pcp(), 0);
+        assert_eq!(tags[1].protocol(), ether::Protocol::Vlan);
+        assert_eq!(tags[1].vid(), 100);
+        assert_eq!(tags[1].pcp(), 5);
+
+        let ip = ip::v4::Packet::new(ether.payload()).unwrap();
+        assert!(ip.is_valid());
+        assert_eq!(ip.protocol(), ip::Protocol::Udp);
+
+        assert!(ether::Builder::default().vlan(0x1000, 0).is_err());
+        assert!(ether::Builder::default().vlan(1, 8).is_err());
+        assert!(ether::Builder::default()
+            .vlan(1, 0)
+            .unwrap()
+            .vlan(2, 0)
+            .unwrap()
+            .vlan(3, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn arp() {
+        use util::MacAddr;
+
+        let packet = ether::Builder::default()
+            .destination("ff:ff:ff:ff:ff:ff".parse().unwrap())
+            .unwrap()
+            .source("e4:b3:18:26:63:a3".parse().unwrap())
+            .unwrap()
+            .arp()
+            .unwrap()
+            .request()
+            .unwrap()
+            .source(
+                MacAddr::from([0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3]),
+                Ipv4Addr::new(192, 168, 0, 79),
+            )
+            .unwrap()
+            .ip_to_find(Ipv4Addr::new(192, 168, 0, 1))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ether = ether::Packet::new(packet).unwrap();
+        assert_eq!(ether.protocol(), ether::Protocol::Arp);
+        assert_eq!(ether.payload().len(), 28);
+    }
+
+    #[test]
+    fn fcs() {
+        let packet = ether::Builder::default()
+            .destination("ff:ff:ff:ff:ff:ff".parse().unwrap())
+            .unwrap()
+            .source("e4:b3:18:26:63:a3".parse().unwrap())
+            .unwrap()
+            .fcs()
+            .unwrap()
+            .ip()
+            .unwrap()
+            .v4()
+            .unwrap()
+            .ttl(64)
+            .unwrap()
+            .source("192.168.0.79".parse().unwrap())
+            .unwrap()
+            .destination("192.168.0.1".parse().unwrap())
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 64);
+        assert!(ether::verify(&packet));
+
+        let ether = ether::Packet::with_fcs(&packet[..]).unwrap();
+        assert!(ether.is_valid());
+        assert_eq!(ether.payload().len(), 46);
+
+        let ip = ip::v4::Packet::new(ether.payload()).unwrap();
+        assert!(ip.is_valid());
+        assert_eq!(ip.length(), 28);
+
+        let udp = udp::Packet::new(ip.payload()).unwrap();
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+
+        let packet = ether::Builder::default()
+            .protocol(ether::Protocol::Ipv4)
+            .unwrap()
+            .fcs()
+            .unwrap()
+            .payload(&[0xaa; 100])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 118);
+        assert!(ether::verify(&packet));
+        assert!(ether::Builder::default().fcs().unwrap().fcs().is_err());
+    }
 }
@@ -0,0 +1,373 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM};
+use crate::ether::Protocol;
+
+/// IEEE 802.2 LLC header parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  3,
+		max:  4,
+		size: p => if p.is_unnumbered() { 3 } else { 4 },
+	}
+
+	payload {
+		min:  0,
+		max:  1497,
+		size: p => p.buffer.as_ref().len() - if p.is_unnumbered() { 3 } else { 4 },
+	});
+
+/// Service Access Point.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Sap {
+	///
+	Null,
+
+	///
+	Ip,
+
+	///
+	Stp,
+
+	///
+	Snap,
+
+	///
+	Ipx,
+
+	///
+	NetBios,
+
+	///
+	Iso,
+
+	///
+	Global,
+
+	///
+	Unknown(u8),
+}
+
+impl From<u8> for Sap {
+	fn from(value: u8) -> Sap {
+		use self::Sap::*;
+
+		match value {
+			0x00 => Null,
+			0x06 => Ip,
+			0x42 => Stp,
+			0xaa => Snap,
+			0xe0 => Ipx,
+			0xf0 => NetBios,
+			0xfe => Iso,
+			0xff => Global,
+			n    => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Sap {
+	fn into(self) -> u8 {
+		use self::Sap::*;
+
+		match self {
+			Null       => 0x00,
+			Ip         => 0x06,
+			Stp        => 0x42,
+			Snap       => 0xaa,
+			Ipx        => 0xe0,
+			NetBios    => 0xf0,
+			Iso        => 0xfe,
+			Global     => 0xff,
+			Unknown(n) => n,
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ether::llc::Packet")
+			.field("dsap", &self.dsap())
+			.field("ssap", &self.ssap())
+			.field("control", &self.control())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an LLC header without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an LLC header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if !packet.is_unnumbered() && packet.buffer.as_ref().len() < 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+
+	/// Convert the packet to its owned version.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.buffer.as_mut()
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		let header = if self.is_unnumbered() { 3 } else { 4 };
+		self.buffer.as_ref().split_at(header)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		let header = if self.is_unnumbered() { 3 } else { 4 };
+		self.buffer.as_mut().split_at_mut(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Destination Service Access Point.
+	pub fn dsap(&self) -> Sap {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Check if the destination is a group address.
+	pub fn is_group(&self) -> bool {
+		self.buffer.as_ref()[0] & 1 != 0
+	}
+
+	/// Source Service Access Point.
+	pub fn ssap(&self) -> Sap {
+		(self.buffer.as_ref()[1] & !1).into()
+	}
+
+	/// Check if the packet is a response.
+	pub fn is_response(&self) -> bool {
+		self.buffer.as_ref()[1] & 1 != 0
+	}
+
+	/// Check if the control field is in the unnumbered format, which is one
+	/// octet long instead of two.
+	pub fn is_unnumbered(&self) -> bool {
+		self.buffer.as_ref()[2] & 0b11 == 0b11
+	}
+
+	/// Control field.
+	pub fn control(&self) -> u16 {
+		if self.is_unnumbered() {
+			self.buffer.as_ref()[2] as u16
+		}
+		else {
+			(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap()
+		}
+	}
+
+	/// Parse the SNAP header, if the packet is addressed to the SNAP SAP.
+	pub fn snap(&self) -> Result<Snap<&[u8]>> {
+		if self.dsap() != Sap::Snap || self.ssap() != Sap::Snap || !self.is_unnumbered() {
+			Err(Error::InvalidPacket)?
+		}
+
+		Snap::new(self.payload())
+	}
+}
+
+/// IEEE 802 SNAP header parser.
+pub struct Snap<B> {
+	buffer: B,
+}
+
+sized!(Snap,
+	header {
+		min:  5,
+		max:  5,
+		size: 5,
+	}
+
+	payload {
+		min:  0,
+		max:  1492,
+		size: p => p.buffer.as_ref().len() - 5,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Snap<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ether::llc::Snap")
+			.field("oui", &self.oui())
+			.field("id", &self.id())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Snap<B> {
+	/// Create a SNAP header without checking the buffer.
+	pub fn unchecked(buffer: B) -> Snap<B> {
+		Snap { buffer }
+	}
+
+	/// Parse a SNAP header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Snap<B>> {
+		use crate::size::header::Min;
+
+		let packet = Snap::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+
+	/// Convert the packet to its owned version.
+	pub fn to_owned(&self) -> Snap<Vec<u8>> {
+		Snap::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Snap<B> {
+	fn as_ref(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Snap<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.buffer.as_mut()
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Snap<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(5)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Snap<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(5)
+	}
+}
+
+impl<B: AsRef<[u8]>> Snap<B> {
+	/// Organizationally Unique Identifier.
+	pub fn oui(&self) -> [u8; 3] {
+		let mut oui = [0u8; 3];
+		oui.copy_from_slice(&self.buffer.as_ref()[0 .. 3]);
+
+		oui
+	}
+
+	/// Protocol identifier, defined by the organization.
+	pub fn id(&self) -> u16 {
+		(&self.buffer.as_ref()[3 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Protocol of the inner packet, if the protocol identifier is an
+	/// EtherType as per RFC 1042 or 802.1H.
+	pub fn protocol(&self) -> Option<Protocol> {
+		match self.oui() {
+			[0x00, 0x00, 0x00] | [0x00, 0x00, 0xf8] =>
+				Some(self.id().into()),
+
+			_ =>
+				None
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ether::llc;
+
+	#[test]
+	fn stp() {
+		let raw = [0x01u8, 0x80, 0xc2, 0x00, 0x00, 0x00, 0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e, 0x00, 0x26, 0x42, 0x42, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e, 0x80, 0x01, 0x00, 0x00, 0x14, 0x00, 0x02, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+		let ether = ether::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Length(38));
+		assert_eq!(ether.length(), Some(38));
+		assert_eq!(ether.payload().len(), 38);
+
+		let llc = ether.llc().unwrap();
+		assert_eq!(llc.dsap(), llc::Sap::Stp);
+		assert_eq!(llc.ssap(), llc::Sap::Stp);
+		assert!(llc.is_unnumbered());
+		assert_eq!(llc.control(), 0x03);
+		assert_eq!(llc.payload().len(), 35);
+		assert!(llc.snap().is_err());
+	}
+
+	#[test]
+	fn cdp() {
+		let raw = [0x01u8, 0x00, 0x0c, 0xcc, 0xcc, 0xcc, 0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e, 0x00, 0x0c, 0xaa, 0xaa, 0x03, 0x00, 0x00, 0x0c, 0x20, 0x00, 0x02, 0xb4, 0xde, 0xad];
+
+		let ether = ether::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ether.length(), Some(12));
+
+		let llc  = ether.llc().unwrap();
+		let snap = llc.snap().unwrap();
+		assert_eq!(snap.oui(), [0x00, 0x00, 0x0c]);
+		assert_eq!(snap.id(), 0x2000);
+		assert_eq!(snap.protocol(), None);
+		assert_eq!(snap.payload(), &[0x02, 0xb4, 0xde, 0xad]);
+	}
+
+	#[test]
+	fn rfc1042() {
+		let raw = [0xaau8, 0xaa, 0x03, 0x00, 0x00, 0x00, 0x08, 0x06];
+
+		let llc = llc::Packet::new(&raw[..]).unwrap();
+		assert_eq!(llc.snap().unwrap().protocol(), Some(ether::Protocol::Arp));
+
+		let ether = ether::Packet::new(&[0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x08, 0x00][..]).unwrap();
+		assert_eq!(ether.length(), None);
+		assert!(ether.llc().is_err());
+	}
+}
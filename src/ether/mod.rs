@@ -20,3 +20,74 @@ pub use self::packet::Packet;
 
 mod builder;
 pub use self::builder::Builder;
+
+/// 802.1Q VLAN tags.
+pub mod vlan;
+
+/// IEEE 802.2 LLC and SNAP headers.
+pub mod llc;
+
+/// Calculate the frame check sequence for an Ethernet frame, the buffer must
+/// not include the FCS itself.
+pub fn checksum(buffer: &[u8]) -> u32 {
+	const TABLE: [u32; 256] = {
+		let mut table = [0u32; 256];
+		let mut i     = 0;
+
+		while i < 256 {
+			let mut value = i as u32;
+			let mut bit   = 0;
+
+			while bit < 8 {
+				value = if value & 1 != 0 { (value >> 1) ^ 0xedb8_8320 } else { value >> 1 };
+				bit  += 1;
+			}
+
+			table[i] = value;
+			i       += 1;
+		}
+
+		table
+	};
+
+	let mut result = 0xffff_ffffu32;
+
+	for byte in buffer {
+		result = TABLE[((result ^ u32::from(*byte)) & 0xff) as usize] ^ (result >> 8);
+	}
+
+	!result
+}
+
+/// Check the frame check sequence of an Ethernet frame, the buffer must
+/// include the trailing FCS.
+pub fn verify(buffer: &[u8]) -> bool {
+	use byteorder::{ReadBytesExt, LittleEndian};
+
+	if buffer.len() < 4 {
+		return false;
+	}
+
+	let (frame, mut fcs) = buffer.split_at(buffer.len() - 4);
+	fcs.read_u32::<LittleEndian>().unwrap() == checksum(frame)
+}
+
+#[cfg(test)]
+mod test {
+	use crate::ether;
+
+	#[test]
+	fn checksum() {
+		assert_eq!(ether::checksum(b"123456789"), 0xcbf4_3926);
+
+		let raw = [0xffu8, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x20, 0xaf, 0xb7, 0x80, 0xb8, 0x08, 0x06, 0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01, 0x00, 0x20, 0xaf, 0xb7, 0x80, 0xb8, 0xc0, 0xa8, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0xa8, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+		let mut frame = raw.to_vec();
+		frame.extend_from_slice(&ether::checksum(&raw).to_le_bytes());
+		assert!(ether::verify(&frame));
+
+		frame[20] ^= 1;
+		assert!(!ether::verify(&frame));
+		assert!(!ether::verify(&[0x00]));
+	}
+}
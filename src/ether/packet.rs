@@ -14,29 +14,37 @@
 
 use std::fmt;
 use std::io::Cursor;
-use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian, LittleEndian};
 use hwaddr::HwAddr;
 
 use crate::error::*;
+use crate::buffer;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
 use crate::ether::Protocol;
+use crate::ether::vlan;
+use crate::ether::llc;
+
+/// Maximum number of stacked 802.1Q tags, an 802.1ad service tag and a
+/// customer tag.
+pub(crate) const MAX_TAGS: usize = 2;
 
 /// Ethernet frame parser.
 pub struct Packet<B> {
 	pub(crate) buffer: B,
+	fcs: bool,
 }
 
 sized!(Packet,
 	header {
 		min:  14,
-		max:  14,
-		size: 14,
+		max:  22,
+		size: p => p.offset() + 2,
 	}
 
 	payload {
 		min:  0,
 		max:  1486,
-		size: p => p.buffer.as_ref().len() - 14,
+		size: p => p.end() - (p.offset() + 2),
 	});
 
 impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
@@ -45,7 +53,9 @@ impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
 			.field("destination", &self.source())
 			.field("source", &self.destination())
 			.field("protocol", &self.protocol())
+			.field("tags", &self.tags().collect::<Vec<_>>())
 			.field("payload", &self.payload())
+			.field("fcs", &self.fcs())
 			.finish()
 	}
 }
@@ -53,19 +63,42 @@ impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
 impl<B: AsRef<[u8]>> Packet<B> {
 	/// Create an Ethernet frame without checking the buffer.
 	pub fn unchecked(buffer: B) -> Packet<B> {
-		Packet { buffer }
+		Packet { buffer, fcs: false }
 	}
 
 	/// Parse an Ethernet frame, checking the buffer contents are correct.
 	pub fn new(buffer: B) -> Result<Packet<B>> {
+		Packet::parse(buffer, false)
+	}
+
+	/// Parse an Ethernet frame followed by its 4 bytes frame check sequence,
+	/// checking the buffer contents are correct.
+	///
+	/// # Note
+	///
+	/// The FCS is excluded from the payload, but it's not verified, use
+	/// `is_valid` to do so.
+	pub fn with_fcs(buffer: B) -> Result<Packet<B>> {
+		Packet::parse(buffer, true)
+	}
+
+	fn parse(buffer: B, fcs: bool) -> Result<Packet<B>> {
 		use crate::size::header::Min;
 
-		let packet = Packet::unchecked(buffer);
+		let packet = Packet { buffer, fcs };
 
-		if packet.buffer.as_ref().len() < Self::min() {
+		if packet.frame().len() < Self::min() {
 			Err(Error::SmallBuffer)?
 		}
 
+		for tag in packet.tags() {
+			tag?;
+		}
+
+		if packet.tags().count() > MAX_TAGS {
+			Err(Error::InvalidPacket)?
+		}
+
 		Ok(packet)
 	}
 }
@@ -78,7 +111,10 @@ impl<B: AsRef<[u8]>> Packet<B> {
 	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
 	/// implements `Clone` and the impl would conflict.
 	pub fn to_owned(&self) -> Packet<Vec<u8>> {
-		Packet::unchecked(self.buffer.as_ref().to_vec())
+		Packet {
+			buffer: self.buffer.as_ref().to_vec(),
+			fcs:    self.fcs,
+		}
 	}
 }
 
@@ -113,17 +149,48 @@ impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for
 
 impl<B: AsRef<[u8]>> P for Packet<B> {
 	fn split(&self) -> (&[u8], &[u8]) {
-		self.buffer.as_ref().split_at(14)
+		let (offset, end) = (self.offset(), self.end());
+		self.buffer.as_ref()[.. end].split_at(offset + 2)
 	}
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
 	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
-		self.buffer.as_mut().split_at_mut(14)
+		let (offset, end) = (self.offset(), self.end());
+		self.buffer.as_mut()[.. end].split_at_mut(offset + 2)
 	}
 }
 
 impl<B: AsRef<[u8]>> Packet<B> {
+	/// Offset of the EtherType following any 802.1Q tags.
+	fn offset(&self) -> usize {
+		12 + self.tags().take(MAX_TAGS).take_while(|t| t.is_ok()).count() * 4
+	}
+
+	/// The frame without the FCS.
+	fn frame(&self) -> &[u8] {
+		let buffer = self.buffer.as_ref();
+
+		if self.fcs {
+			&buffer[.. buffer.len().saturating_sub(4)]
+		}
+		else {
+			buffer
+		}
+	}
+
+	/// End of the frame, excluding any padding after an IEEE 802.3 payload.
+	fn end(&self) -> usize {
+		let length = self.frame().len();
+
+		if let Some(payload) = self.length() {
+			length.min(self.offset() + 2 + payload as usize)
+		}
+		else {
+			length
+		}
+	}
+
 	/// MAC address for the destination.
 	pub fn destination(&self) -> HwAddr {
 		self.buffer.as_ref()[0 .. 6].into()
@@ -135,9 +202,64 @@ impl<B: AsRef<[u8]>> Packet<B> {
 	}
 
 	/// Protocol of the inner packet.
+	///
+	/// # Note
+	///
+	/// For tagged frames this is the Tag Protocol Identifier of the outermost
+	/// tag, use `inner_protocol` to skip the tags.
 	pub fn protocol(&self) -> Protocol {
 		(&self.buffer.as_ref()[12 ..]).read_u16::<BigEndian>().unwrap().into()
 	}
+
+	/// Length of the payload for IEEE 802.3 frames, `None` for Ethernet II
+	/// frames.
+	pub fn length(&self) -> Option<u16> {
+		match self.inner_protocol() {
+			Protocol::Length(length) =>
+				Some(length),
+
+			_ =>
+				None
+		}
+	}
+
+	/// Parse the LLC header of an IEEE 802.3 frame.
+	pub fn llc(&self) -> Result<llc::Packet<&[u8]>> {
+		if self.length().is_none() {
+			Err(Error::InvalidPacket)?
+		}
+
+		llc::Packet::new(self.payload())
+	}
+
+	/// Iterator over the 802.1Q tags, from the outermost to the innermost.
+	pub fn tags(&self) -> vlan::TagIter<'_> {
+		vlan::TagIter {
+			buffer: &self.frame()[12 ..],
+		}
+	}
+
+	/// Frame check sequence, if the frame has one.
+	pub fn fcs(&self) -> Option<u32> {
+		if self.fcs {
+			let buffer = self.buffer.as_ref();
+			Some((&buffer[buffer.len() - 4 ..]).read_u32::<LittleEndian>().unwrap())
+		}
+		else {
+			None
+		}
+	}
+
+	/// Check if the frame check sequence is valid, frames without one are
+	/// always valid.
+	pub fn is_valid(&self) -> bool {
+		!self.fcs || super::verify(self.buffer.as_ref())
+	}
+
+	/// Protocol of the inner packet, after any 802.1Q tags.
+	pub fn inner_protocol(&self) -> Protocol {
+		(&self.buffer.as_ref()[self.offset() ..]).read_u16::<BigEndian>().unwrap().into()
+	}
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
@@ -162,6 +284,157 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 
 		Ok(self)
 	}
+
+	/// Mutable iterator over the 802.1Q tags, from the outermost to the
+	/// innermost.
+	///
+	/// # Note
+	///
+	/// The frame check sequence, if any, has to be updated afterwards.
+	pub fn tags_mut(&mut self) -> vlan::TagIterMut<'_> {
+		let end = self.frame().len();
+
+		vlan::TagIterMut {
+			buffer: &mut self.buffer.as_mut()[12 .. end],
+		}
+	}
+
+	/// Frame check sequence.
+	pub fn set_fcs(&mut self, value: u32) -> Result<&mut Self> {
+		if !self.fcs {
+			Err(Error::InvalidPacket)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		let length = buffer.len();
+
+		Cursor::new(&mut buffer[length - 4 ..])
+			.write_u32::<LittleEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Recalculate and set the frame check sequence.
+	pub fn update_fcs(&mut self) -> Result<&mut Self> {
+		let checksum = super::checksum(self.frame());
+		self.set_fcs(checksum)
+	}
+}
+
+/// Write an 802.1Q tag in a frame with 4 bytes of space in front.
+fn push(buffer: &mut [u8], vid: u16, pcp: u8) -> Result<()> {
+	let protocol = Packet::unchecked(&buffer[4 ..]).protocol();
+	buffer.copy_within(4 .. 16, 0);
+
+	vlan::Tag::unchecked(&mut buffer[12 ..])
+		.set_protocol(if protocol.is_vlan() { Protocol::PBridge } else { Protocol::Vlan })?
+		.set_pcp(pcp)?
+		.set_vid(vid)?;
+
+	Ok(())
+}
+
+/// Remove the outermost 802.1Q tag from a frame, leaving 4 bytes of space in
+/// front.
+fn pop(buffer: &mut [u8]) -> Option<vlan::Tag<[u8; 4]>> {
+	let mut tag = [0u8; 4];
+
+	match Packet::unchecked(&buffer[..]).tags().next() {
+		Some(Ok(_)) =>
+			tag.copy_from_slice(&buffer[12 .. 16]),
+
+		_ =>
+			return None
+	}
+
+	buffer.copy_within(0 .. 12, 4);
+
+	Some(vlan::Tag::unchecked(tag))
+}
+
+impl Packet<Vec<u8>> {
+	/// Push an 802.1Q tag with the given VLAN Identifier and Priority Code Point
+	/// as the outermost tag.
+	///
+	/// If the frame is already tagged the new tag is marked as an 802.1ad
+	/// service tag, at most two tags can be stacked.
+	pub fn push_vlan(&mut self, vid: u16, pcp: u8) -> Result<&mut Self> {
+		if vid > 0xfff || pcp > 0b111 {
+			Err(Error::InvalidValue)?
+		}
+
+		if self.tags().count() >= MAX_TAGS {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.buffer.splice(0 .. 0, [0u8; 4].iter().cloned());
+		push(&mut self.buffer, vid, pcp)?;
+
+		if self.fcs {
+			self.update_fcs()?;
+		}
+
+		Ok(self)
+	}
+
+	/// Pop the outermost 802.1Q tag, if any.
+	pub fn pop_vlan(&mut self) -> Result<Option<vlan::Tag<[u8; 4]>>> {
+		if let Some(tag) = pop(&mut self.buffer) {
+			self.buffer.drain(.. 4);
+
+			if self.fcs {
+				self.update_fcs()?;
+			}
+
+			Ok(Some(tag))
+		}
+		else {
+			Ok(None)
+		}
+	}
+}
+
+impl<'a> Packet<buffer::Slice<'a>> {
+	/// Push an 802.1Q tag with the given VLAN Identifier and Priority Code Point
+	/// as the outermost tag, taking space from the buffer headroom.
+	///
+	/// If the frame is already tagged the new tag is marked as an 802.1ad
+	/// service tag, at most two tags can be stacked.
+	pub fn push_vlan(&mut self, vid: u16, pcp: u8) -> Result<&mut Self> {
+		if vid > 0xfff || pcp > 0b111 {
+			Err(Error::InvalidValue)?
+		}
+
+		if self.tags().count() >= MAX_TAGS {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.buffer.prepend(4)?;
+		push(&mut self.buffer, vid, pcp)?;
+
+		if self.fcs {
+			self.update_fcs()?;
+		}
+
+		Ok(self)
+	}
+
+	/// Pop the outermost 802.1Q tag, if any, giving the space back to the
+	/// buffer headroom.
+	pub fn pop_vlan(&mut self) -> Result<Option<vlan::Tag<[u8; 4]>>> {
+		if let Some(tag) = pop(&mut self.buffer) {
+			self.buffer.strip(4)?;
+
+			if self.fcs {
+				self.update_fcs()?;
+			}
+
+			Ok(Some(tag))
+		}
+		else {
+			Ok(None)
+		}
+	}
 }
 
 #[cfg(test)]
@@ -185,5 +458,131 @@ mod test {
 		assert_eq!(ether.destination(), "00:23:69:63:59:be".parse().unwrap());
 		assert_eq!(ether.source(), "e4:b3:18:26:63:a3".parse().unwrap());
 		assert_eq!(ether.protocol(), ether::Protocol::Ipv4);
+		assert_eq!(ether.inner_protocol(), ether::Protocol::Ipv4);
+		assert_eq!(ether.tags().count(), 0);
+	}
+
+	#[test]
+	fn qinq() {
+		let raw = [0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x88, 0xa8, 0x00, 0x0a, 0x81, 0x00, 0xa0, 0x64, 0x86, 0xdd, 0xde, 0xad];
+
+		let ether = ether::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::PBridge);
+		assert_eq!(ether.inner_protocol(), ether::Protocol::Ipv6);
+		assert_eq!(ether.header().len(), 22);
+		assert_eq!(ether.payload(), &[0xde, 0xad]);
+
+		let tags = ether.tags().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(tags.len(), 2);
+		assert_eq!(tags[0].protocol(), ether::Protocol::PBridge);
+		assert_eq!(tags[0].vid(), 10);
+		assert_eq!(tags[1].protocol(), ether::Protocol::Vlan);
+		assert_eq!(tags[1].pcp(), 5);
+		assert_eq!(tags[1].vid(), 100);
+
+		assert!(ether::Packet::new(&raw[.. 18]).is_err());
+
+		// Only two tags can be stacked.
+		let raw = [0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x88, 0xa8, 0x00, 0x0a, 0x88, 0xa8, 0x00, 0x14, 0x81, 0x00, 0xa0, 0x64, 0x86, 0xdd, 0xde, 0xad];
+
+		assert!(ether::Packet::new(&raw[..]).is_err());
+		assert_eq!(ether::Packet::unchecked(&raw[..]).header().len(), 22);
+	}
+
+	#[test]
+	fn push_pop() {
+		let raw = [0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x86, 0xdd, 0xde, 0xad];
+
+		let mut ether = ether::Packet::new(raw.to_vec()).unwrap();
+		assert!(ether.pop_vlan().unwrap().is_none());
+
+		ether.push_vlan(100, 5).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Vlan);
+		assert_eq!(ether.inner_protocol(), ether::Protocol::Ipv6);
+		assert_eq!(ether.source(), "e4:b3:18:26:63:a3".parse().unwrap());
+		assert_eq!(ether.payload(), &[0xde, 0xad]);
+
+		ether.push_vlan(10, 0).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::PBridge);
+		assert_eq!(ether.tags().count(), 2);
+
+		assert!(ether.push_vlan(30, 0).is_err());
+		assert_eq!(ether.tags().count(), 2);
+
+		{
+			let mut tags = ether.tags_mut();
+			tags.next().unwrap().unwrap().set_vid(20).unwrap();
+			tags.next().unwrap().unwrap().set_pcp(1).unwrap();
+		}
+
+		let outer = ether.pop_vlan().unwrap().unwrap();
+		assert_eq!(outer.protocol(), ether::Protocol::PBridge);
+		assert_eq!(outer.vid(), 20);
+
+		let inner = ether.pop_vlan().unwrap().unwrap();
+		assert_eq!(inner.pcp(), 1);
+		assert_eq!(inner.vid(), 100);
+
+		assert_eq!(ether.as_ref(), &raw[..]);
+		assert!(ether.push_vlan(0x1000, 0).is_err());
+	}
+
+	#[test]
+	fn push_pop_headroom() {
+		use crate::buffer;
+
+		let frame = [0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x08, 0x00, 0xde, 0xad];
+
+		let mut raw = [0u8; 20];
+		raw[4 ..].copy_from_slice(&frame);
+
+		{
+			let buffer = buffer::Slice::with_headroom(&mut raw, 4).unwrap();
+			let mut ether = ether::Packet::new(buffer).unwrap();
+
+			ether.push_vlan(42, 3).unwrap();
+			assert_eq!(ether.inner_protocol(), ether::Protocol::Ipv4);
+			assert_eq!(ether.destination(), "00:23:69:63:59:be".parse().unwrap());
+			assert_eq!(ether.payload(), &[0xde, 0xad]);
+			assert!(ether.push_vlan(43, 0).is_err());
+		}
+
+		assert_eq!(&raw[12 .. 16], &[0x81, 0x00, 0x60, 0x2a]);
+
+		{
+			let buffer    = buffer::Slice::with_headroom(&mut raw, 0).unwrap();
+			let mut ether = ether::Packet::new(buffer).unwrap();
+			assert_eq!(ether.pop_vlan().unwrap().unwrap().vid(), 42);
+			assert_eq!(ether.as_ref(), &frame[..]);
+		}
+	}
+
+	#[test]
+	fn fcs() {
+		let raw = [0x00u8, 0x23, 0x69, 0x63, 0x59, 0xbe, 0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3, 0x86, 0xdd, 0xde, 0xad];
+
+		let mut frame = raw.to_vec();
+		frame.extend_from_slice(&ether::checksum(&raw).to_le_bytes());
+
+		let mut ether = ether::Packet::with_fcs(frame).unwrap();
+		assert!(ether.is_valid());
+		assert_eq!(ether.fcs(), Some(ether::checksum(&raw)));
+		assert_eq!(ether.payload(), &[0xde, 0xad]);
+
+		ether.push_vlan(100, 0).unwrap();
+		assert!(ether.is_valid());
+		assert_eq!(ether.payload(), &[0xde, 0xad]);
+
+		ether.tags_mut().next().unwrap().unwrap().set_vid(200).unwrap();
+		assert!(!ether.is_valid());
+		ether.update_fcs().unwrap();
+		assert!(ether.is_valid());
+
+		ether.pop_vlan().unwrap();
+		assert!(ether.is_valid());
+		assert_eq!(ether.fcs(), Some(ether::checksum(&raw)));
+
+		assert!(ether::Packet::new(&raw[..]).unwrap().fcs().is_none());
+		assert!(ether::Packet::with_fcs(&raw[.. 14]).is_err());
 	}
 }
@@ -84,10 +84,28 @@ pub enum Protocol {
 	///
 	QinQ,
 
+	/// Length of an IEEE 802.3 frame, the payload starts with an LLC header.
+	Length(u16),
+
 	///
 	Unknown(u16),
 }
 
+impl Protocol {
+	/// Check if the protocol is an 802.1Q tag.
+	pub fn is_vlan(&self) -> bool {
+		match *self {
+			Protocol::Vlan |
+			Protocol::PBridge |
+			Protocol::QinQ =>
+				true,
+
+			_ =>
+				false
+		}
+	}
+}
+
 impl From<u16> for Protocol {
 	fn from(value: u16) -> Protocol {
 		use self::Protocol::*;
@@ -116,6 +134,7 @@ impl From<u16> for Protocol {
 			0x88f7 => Ptp,
 			0x8902 => Cfm,
 			0x9100 => QinQ,
+			n @ 0x0000 ..= 0x05dc => Length(n),
 			n      => Unknown(n),
 		}
 	}
@@ -149,6 +168,7 @@ impl Into<u16> for Protocol {
 			Ptp            => 0x88f7,
 			Cfm            => 0x8902,
 			QinQ           => 0x9100,
+			Length(n)      => n,
 			Unknown(n)     => n,
 		}
 	}
@@ -0,0 +1,226 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::ether::Protocol;
+
+/// 802.1Q tag parser, made of the Tag Protocol Identifier followed by the Tag
+/// Control Information.
+pub struct Tag<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Tag<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ether::vlan::Tag")
+			.field("protocol", &self.protocol())
+			.field("pcp", &self.pcp())
+			.field("dei", &self.dei())
+			.field("vid", &self.vid())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+	/// Create a tag without checking the buffer.
+	pub fn unchecked(buffer: B) -> Tag<B> {
+		Tag { buffer }
+	}
+
+	/// Parse a tag, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Tag<B>> {
+		if buffer.as_ref().len() < 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Tag::unchecked(buffer))
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Tag<B> {
+	fn as_ref(&self) -> &[u8] {
+		&self.buffer.as_ref()[.. 4]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Tag<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.buffer.as_mut()[.. 4]
+	}
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+	/// Tag Protocol Identifier.
+	pub fn protocol(&self) -> Protocol {
+		(&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Priority Code Point.
+	pub fn pcp(&self) -> u8 {
+		self.buffer.as_ref()[2] >> 5
+	}
+
+	/// Drop Eligible Indicator.
+	pub fn dei(&self) -> bool {
+		self.buffer.as_ref()[2] & 0b1_0000 != 0
+	}
+
+	/// VLAN Identifier.
+	pub fn vid(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap() & 0xfff
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Tag<B> {
+	/// Tag Protocol Identifier.
+	pub fn set_protocol(&mut self, value: Protocol) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[0 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Priority Code Point.
+	pub fn set_pcp(&mut self, value: u8) -> Result<&mut Self> {
+		if value > 0b111 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[2] = (buffer[2] & 0b1_1111) | (value << 5);
+
+		Ok(self)
+	}
+
+	/// Drop Eligible Indicator.
+	pub fn set_dei(&mut self, value: bool) -> Result<&mut Self> {
+		let buffer = self.buffer.as_mut();
+
+		if value {
+			buffer[2] |= 0b1_0000;
+		}
+		else {
+			buffer[2] &= !0b1_0000;
+		}
+
+		Ok(self)
+	}
+
+	/// VLAN Identifier.
+	pub fn set_vid(&mut self, value: u16) -> Result<&mut Self> {
+		if value > 0xfff {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[2] = (buffer[2] & 0xf0) | (value >> 8) as u8;
+		buffer[3] = value as u8;
+
+		Ok(self)
+	}
+}
+
+/// Iterator over stacked 802.1Q tags.
+pub struct TagIter<'a> {
+	pub(crate) buffer: &'a [u8],
+}
+
+impl<'a> Iterator for TagIter<'a> {
+	type Item = Result<Tag<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.len() < 2 {
+			return None;
+		}
+
+		let protocol = Protocol::from((&self.buffer[0 ..]).read_u16::<BigEndian>().unwrap());
+
+		if !protocol.is_vlan() {
+			return None;
+		}
+
+		// The tag has to be followed by at least another EtherType.
+		if self.buffer.len() < 6 {
+			self.buffer = &[];
+			return Some(Err(Error::SmallBuffer));
+		}
+
+		let (tag, rest) = self.buffer.split_at(4);
+		self.buffer = rest;
+
+		Some(Ok(Tag::unchecked(tag)))
+	}
+}
+
+/// Mutable iterator over stacked 802.1Q tags.
+pub struct TagIterMut<'a> {
+	pub(crate) buffer: &'a mut [u8],
+}
+
+impl<'a> Iterator for TagIterMut<'a> {
+	type Item = Result<Tag<&'a mut [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.len() < 2 {
+			return None;
+		}
+
+		let protocol = Protocol::from((&self.buffer[0 ..]).read_u16::<BigEndian>().unwrap());
+
+		if !protocol.is_vlan() {
+			return None;
+		}
+
+		let buffer = std::mem::take(&mut self.buffer);
+
+		if buffer.len() < 6 {
+			return Some(Err(Error::SmallBuffer));
+		}
+
+		let (tag, rest) = buffer.split_at_mut(4);
+		self.buffer = rest;
+
+		Some(Ok(Tag::unchecked(tag)))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::ether;
+
+	#[test]
+	fn tag() {
+		let mut raw = [0x81u8, 0x00, 0xa0, 0x64];
+
+		{
+			let tag = ether::vlan::Tag::new(&raw[..]).unwrap();
+			assert_eq!(tag.protocol(), ether::Protocol::Vlan);
+			assert_eq!(tag.pcp(), 5);
+			assert!(!tag.dei());
+			assert_eq!(tag.vid(), 100);
+		}
+
+		{
+			let mut tag = ether::vlan::Tag::new(&mut raw[..]).unwrap();
+			tag.set_pcp(3).unwrap().set_dei(true).unwrap().set_vid(0xabc).unwrap();
+			assert!(tag.set_pcp(8).is_err());
+			assert!(tag.set_vid(0x1000).is_err());
+		}
+
+		assert_eq!(raw, [0x81, 0x00, 0x7a, 0xbc]);
+	}
+}
@@ -19,7 +19,7 @@ use crate::error::*;
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::icmp::checksum;
-use crate::icmp::{echo, timestamp, information};
+use crate::icmp::{echo, timestamp, information, previous};
 
 /// ICMP packet builder.
 #[derive(Debug)]
@@ -75,6 +75,15 @@ impl<B: Buffer> Builder<B> {
 
 		Ok(timestamp)
 	}
+
+	/// Create a Source Quench, Destination Unreachable or Time Exceeded
+	/// packet.
+	pub fn previous(self) -> Result<previous::Builder<B>> {
+		let mut previous = previous::Builder::with(self.buffer)?;
+		previous.finalizer().extend(self.finalizer);
+
+		Ok(previous)
+	}
 }
 
 pub(in crate::icmp) fn prepare<B: Buffer>(finalizer: &mut Finalization, buffer: &B) {
@@ -67,6 +67,19 @@ pub enum DestinationUnreachable {
 	Unknown(u8),
 }
 
+/// Codes for Time Exceeded packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimeExceeded {
+	///
+	TtlExpiredInTransit,
+
+	///
+	FragmentReassemblyTimeExceeded,
+
+	///
+	Unknown(u8),
+}
+
 /// Codes for Redirect Message packets.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub enum RedirectMessage {
@@ -154,6 +167,30 @@ impl Into<u8> for DestinationUnreachable {
 	}
 }
 
+impl From<u8> for TimeExceeded {
+	fn from(value: u8) -> Self {
+		use self::TimeExceeded::*;
+
+		match value {
+			0 => TtlExpiredInTransit,
+			1 => FragmentReassemblyTimeExceeded,
+			v => Unknown(v),
+		}
+	}
+}
+
+impl Into<u8> for TimeExceeded {
+	fn into(self) -> u8 {
+		use self::TimeExceeded::*;
+
+		match self {
+			TtlExpiredInTransit            => 0,
+			FragmentReassemblyTimeExceeded => 1,
+			Unknown(v)                     => v,
+		}
+	}
+}
+
 impl From<u8> for RedirectMessage {
 	fn from(value: u8) -> Self {
 		use self::RedirectMessage::*;
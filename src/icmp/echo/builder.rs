@@ -57,9 +57,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 
 		builder::prepare(&mut self.finalizer, &self.buffer);
 
-		let mut buffer = self.buffer.into_inner();
-		self.finalizer.finalize(buffer.as_mut())?;
-		Ok(buffer)
+		self.finalizer.build(self.buffer)
 	}
 }
 
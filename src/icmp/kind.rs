@@ -67,6 +67,23 @@ pub enum Kind {
 	Unknown(u8),
 }
 
+impl Kind {
+	/// Check if the type is an error message.
+	pub fn is_error(&self) -> bool {
+		match *self {
+			Kind::DestinationUnreachable |
+			Kind::SourceQuench |
+			Kind::RedirectMessage |
+			Kind::TimeExceeded |
+			Kind::ParameterProblem =>
+				true,
+
+			_ =>
+				false
+		}
+	}
+}
+
 impl From<u8> for Kind {
 	fn from(value: u8) -> Kind {
 		use self::Kind::*;
@@ -42,6 +42,9 @@ pub mod redirect_message;
 /// Timestamp Request/Reply.
 pub mod timestamp;
 
+mod unreachable;
+pub use self::unreachable::unreachable_for;
+
 /// Calculate the checksum for an ICMP packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
 	use std::io::Cursor;
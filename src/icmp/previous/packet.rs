@@ -13,6 +13,7 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
 
 use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
@@ -142,7 +143,32 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
 
 impl<B: AsRef<[u8]>> Packet<B> {
 	/// Packet to cause the message.
+	///
+	/// # Note
+	///
+	/// The packet is usually truncated, so the payload isn't checked.
 	pub fn packet(&self) -> Result<ip::v4::Packet<&[u8]>> {
-		ip::v4::Packet::new(&self.buffer.as_ref()[8 ..])
+		ip::v4::Packet::no_payload(&self.buffer.as_ref()[8 ..])
+	}
+
+	/// Whether it's a Destination Unreachable packet.
+	pub fn is_destination_unreachable(&self) -> bool {
+		Kind::from(self.buffer.as_ref()[0]) == Kind::DestinationUnreachable
+	}
+
+	/// Whether it's a Time Exceeded packet.
+	pub fn is_time_exceeded(&self) -> bool {
+		Kind::from(self.buffer.as_ref()[0]) == Kind::TimeExceeded
+	}
+
+	/// Whether it's a Source Quench packet.
+	pub fn is_source_quench(&self) -> bool {
+		Kind::from(self.buffer.as_ref()[0]) == Kind::SourceQuench
+	}
+
+	/// Next-hop MTU of a Fragmentation Required message, as defined in RFC
+	/// 1191.
+	pub fn mtu(&self) -> u16 {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap()
 	}
 }
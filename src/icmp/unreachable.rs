@@ -0,0 +1,111 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use crate::error::*;
+use crate::builder::Builder as Build;
+use crate::packet::Packet as P;
+use crate::ip;
+use crate::icmp;
+use crate::icmp::code;
+
+/// Build a Destination Unreachable message in response to the given packet,
+/// quoting its header and the first 8 octets of its payload.
+///
+/// # Errors
+///
+/// As per RFC 1122 no message is generated in response to ICMP error
+/// messages, fragments other than the first, or packets from or to a
+/// broadcast or multicast address, which returns `Error::InvalidPacket`.
+pub fn unreachable_for<B: AsRef<[u8]>>(packet: &ip::v4::Packet<B>, code: code::DestinationUnreachable) -> Result<Vec<u8>> {
+	let source      = packet.source();
+	let destination = packet.destination();
+
+	if packet.offset() != 0 {
+		Err(Error::InvalidPacket)?
+	}
+
+	if source.is_unspecified() || source.is_broadcast() || source.is_multicast() ||
+	   destination.is_broadcast() || destination.is_multicast()
+	{
+		Err(Error::InvalidPacket)?
+	}
+
+	let (header, payload) = packet.split();
+
+	if packet.protocol() == ip::Protocol::Icmp {
+		if let Ok(message) = icmp::Packet::new(payload) {
+			if message.kind().is_error() {
+				Err(Error::InvalidPacket)?
+			}
+		}
+	}
+
+	ip::v4::Builder::default()
+		.ttl(64)?
+		.source(destination)?
+		.destination(source)?
+		.icmp()?.previous()?
+			.destination_unreachable(code)?
+			.packet(header.iter().chain(payload.iter().take(8)))?
+			.build()
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn unreachable() {
+		let packet = ip::v4::Builder::default()
+			.ttl(64).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("10.0.0.1".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(53).unwrap()
+				.payload(&[0xaa; 100]).unwrap()
+				.build().unwrap();
+
+		let packet   = ip::v4::Packet::new(packet).unwrap();
+		let response = icmp::unreachable_for(&packet,
+			icmp::code::DestinationUnreachable::DestinationPortUnreachable).unwrap();
+
+		let response = ip::v4::Packet::new(response).unwrap();
+		assert!(response.is_valid());
+		assert_eq!(response.protocol(), ip::Protocol::Icmp);
+		assert_eq!(response.source(), "10.0.0.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(response.destination(), "192.168.0.79".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(response.length(), 20 + 8 + 28);
+
+		let icmp = icmp::Packet::new(response.payload()).unwrap();
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.kind(), icmp::Kind::DestinationUnreachable);
+		assert_eq!(icmp::code::DestinationUnreachable::from(icmp.code()),
+			icmp::code::DestinationUnreachable::DestinationPortUnreachable);
+
+		let previous = icmp.previous().unwrap();
+		assert!(previous.is_destination_unreachable());
+
+		let quoted = previous.packet().unwrap();
+		assert_eq!(quoted.id(), packet.id());
+		assert_eq!(quoted.destination(), packet.destination());
+		assert_eq!(&response.payload()[28 ..], &packet.payload()[.. 8]);
+
+		assert!(icmp::unreachable_for(&response,
+			icmp::code::DestinationUnreachable::DestinationHostUnreachable).is_err());
+	}
+}
@@ -0,0 +1,220 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::ip;
+use crate::icmpv6::checksum;
+use crate::icmpv6::{echo, previous, packet_too_big, parameter_problem, mld, ndp};
+
+/// Minimum MTU for IPv6 links, ICMPv6 error messages must fit in it.
+pub(in crate::icmpv6) const MINIMUM_MTU: usize = 1280;
+
+/// ICMPv6 packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(buffer: B) -> Result<Self> {
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(self) -> Result<B::Inner> {
+		Err(Error::InvalidPacket)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Build an Echo Request/Reply packet.
+	pub fn echo(self) -> Result<echo::Builder<B>> {
+		let mut echo = echo::Builder::with(self.buffer)?;
+		echo.finalizer().extend(self.finalizer);
+
+		Ok(echo)
+	}
+
+	/// Build a Destination Unreachable or Time Exceeded packet.
+	pub fn previous(self) -> Result<previous::Builder<B>> {
+		let mut previous = previous::Builder::with(self.buffer)?;
+		previous.finalizer().extend(self.finalizer);
+
+		Ok(previous)
+	}
+
+	/// Build a Packet Too Big packet.
+	pub fn packet_too_big(self) -> Result<packet_too_big::Builder<B>> {
+		let mut packet_too_big = packet_too_big::Builder::with(self.buffer)?;
+		packet_too_big.finalizer().extend(self.finalizer);
+
+		Ok(packet_too_big)
+	}
+
+	/// Build a Parameter Problem packet.
+	pub fn parameter_problem(self) -> Result<parameter_problem::Builder<B>> {
+		let mut parameter_problem = parameter_problem::Builder::with(self.buffer)?;
+		parameter_problem.finalizer().extend(self.finalizer);
+
+		Ok(parameter_problem)
+	}
+
+	/// Build a Multicast Listener Query packet.
+	pub fn mld_query(self) -> Result<mld::query::Builder<B>> {
+		let mut query = mld::query::Builder::with(self.buffer)?;
+		query.finalizer().extend(self.finalizer);
+
+		Ok(query)
+	}
+
+	/// Build a Multicast Listener Report/Done packet.
+	pub fn mld_report(self) -> Result<mld::report::Builder<B>> {
+		let mut report = mld::report::Builder::with(self.buffer)?;
+		report.finalizer().extend(self.finalizer);
+
+		Ok(report)
+	}
+
+	/// Build a Version 2 Multicast Listener Report packet.
+	pub fn mld_report_v2(self) -> Result<mld::report_v2::Builder<B>> {
+		let mut report = mld::report_v2::Builder::with(self.buffer)?;
+		report.finalizer().extend(self.finalizer);
+
+		Ok(report)
+	}
+
+	/// Build a Router Solicitation packet.
+	pub fn router_solicitation(self) -> Result<ndp::router_solicitation::Builder<B>> {
+		let mut router_solicitation = ndp::router_solicitation::Builder::with(self.buffer)?;
+		router_solicitation.finalizer().extend(self.finalizer);
+
+		Ok(router_solicitation)
+	}
+
+	/// Build a Router Advertisement packet.
+	pub fn router_advertisement(self) -> Result<ndp::router_advertisement::Builder<B>> {
+		let mut router_advertisement = ndp::router_advertisement::Builder::with(self.buffer)?;
+		router_advertisement.finalizer().extend(self.finalizer);
+
+		Ok(router_advertisement)
+	}
+
+	/// Build a Neighbor Solicitation packet.
+	pub fn neighbor_solicitation(self) -> Result<ndp::neighbor_solicitation::Builder<B>> {
+		let mut neighbor_solicitation = ndp::neighbor_solicitation::Builder::with(self.buffer)?;
+		neighbor_solicitation.finalizer().extend(self.finalizer);
+
+		Ok(neighbor_solicitation)
+	}
+
+	/// Build a Neighbor Advertisement packet.
+	pub fn neighbor_advertisement(self) -> Result<ndp::neighbor_advertisement::Builder<B>> {
+		let mut neighbor_advertisement = ndp::neighbor_advertisement::Builder::with(self.buffer)?;
+		neighbor_advertisement.finalizer().extend(self.finalizer);
+
+		Ok(neighbor_advertisement)
+	}
+
+	/// Build a Redirect packet.
+	pub fn redirect(self) -> Result<ndp::redirect::Builder<B>> {
+		let mut redirect = ndp::redirect::Builder::with(self.buffer)?;
+		redirect.finalizer().extend(self.finalizer);
+
+		Ok(redirect)
+	}
+}
+
+pub(in crate::icmpv6) fn prepare<B: Buffer>(finalizer: &mut Finalization, ip: (usize, usize), buffer: &B) {
+	let offset = buffer.offset();
+	let length = buffer.length();
+
+	finalizer.add(move |out| {
+		let (before, after) = out.split_at_mut(offset);
+		let ip              = &before[ip.0 .. ip.0 + ip.1];
+		let icmp            = &mut after[.. length];
+
+		let checksum = checksum(&ip::v6::Packet::no_payload(ip)?, icmp);
+		Cursor::new(&mut icmp[2 ..])
+			.write_u16::<BigEndian>(checksum)?;
+
+		Ok(())
+	});
+}
+
+/// Append the invoking packet of an error message, truncated so the message
+/// fits in the minimum IPv6 MTU.
+pub(in crate::icmpv6) fn invoking<'a, B: Buffer, T: IntoIterator<Item = &'a u8>>(buffer: &mut B, ip: (usize, usize), value: T) -> Result<()> {
+	let available = MINIMUM_MTU.saturating_sub(buffer.offset() - ip.0 + buffer.length());
+
+	for byte in value.into_iter().take(available) {
+		buffer.more(1)?;
+		*buffer.data_mut().last_mut().unwrap() = *byte;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn echo() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("fe80::2".parse().unwrap()).unwrap()
+			.icmp().unwrap().echo().unwrap().request().unwrap()
+				.identifier(42).unwrap()
+				.sequence(1).unwrap()
+				.payload(b"test").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.next_header(), ip::Protocol::Ipv6Icmp);
+
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmpv6::Kind::EchoRequest);
+		assert!(icmp.is_valid(&ip));
+		assert_eq!(icmp.echo().unwrap().payload(), b"test");
+	}
+
+	#[test]
+	fn standalone() {
+		assert!(icmpv6::Builder::default()
+			.echo().unwrap().request().unwrap()
+			.build().is_err());
+	}
+}
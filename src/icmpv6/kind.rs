@@ -0,0 +1,123 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// ICMPv6 packet types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+	///
+	DestinationUnreachable,
+
+	///
+	PacketTooBig,
+
+	///
+	TimeExceeded,
+
+	///
+	ParameterProblem,
+
+	///
+	EchoRequest,
+
+	///
+	EchoReply,
+
+	///
+	MulticastListenerQuery,
+
+	///
+	MulticastListenerReport,
+
+	///
+	MulticastListenerDone,
+
+	///
+	RouterSolicitation,
+
+	///
+	RouterAdvertisement,
+
+	///
+	NeighborSolicitation,
+
+	///
+	NeighborAdvertisement,
+
+	///
+	Redirect,
+
+	///
+	MulticastListenerReportV2,
+
+	///
+	Unknown(u8),
+}
+
+impl Kind {
+	/// Check if the type is an error message.
+	pub fn is_error(&self) -> bool {
+		let value: u8 = (*self).into();
+		value < 128
+	}
+}
+
+impl From<u8> for Kind {
+	fn from(value: u8) -> Kind {
+		use self::Kind::*;
+
+		match value {
+			1   => DestinationUnreachable,
+			2   => PacketTooBig,
+			3   => TimeExceeded,
+			4   => ParameterProblem,
+			128 => EchoRequest,
+			129 => EchoReply,
+			130 => MulticastListenerQuery,
+			131 => MulticastListenerReport,
+			132 => MulticastListenerDone,
+			133 => RouterSolicitation,
+			134 => RouterAdvertisement,
+			135 => NeighborSolicitation,
+			136 => NeighborAdvertisement,
+			137 => Redirect,
+			143 => MulticastListenerReportV2,
+			v   => Unknown(v),
+		}
+	}
+}
+
+impl Into<u8> for Kind {
+	fn into(self) -> u8 {
+		use self::Kind::*;
+
+		match self {
+			DestinationUnreachable    => 1,
+			PacketTooBig              => 2,
+			TimeExceeded              => 3,
+			ParameterProblem          => 4,
+			EchoRequest               => 128,
+			EchoReply                 => 129,
+			MulticastListenerQuery    => 130,
+			MulticastListenerReport   => 131,
+			MulticastListenerDone     => 132,
+			RouterSolicitation        => 133,
+			RouterAdvertisement       => 134,
+			NeighborSolicitation      => 135,
+			NeighborAdvertisement     => 136,
+			Redirect                  => 137,
+			MulticastListenerReportV2 => 143,
+			Unknown(v)                => v,
+		}
+	}
+}
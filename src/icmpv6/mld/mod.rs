@@ -0,0 +1,56 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Multicast Listener Query, for both MLDv1 and MLDv2.
+pub mod query;
+
+/// Multicast Listener Report and Done (MLDv1).
+pub mod report;
+
+/// Version 2 Multicast Listener Report.
+pub mod report_v2;
+
+/// Multicast address records.
+pub mod record;
+pub use self::record::{Record, RecordIter};
+
+/// Value of the Router Alert option that must be present in the Hop-by-Hop
+/// Options header of every MLD message.
+pub const ROUTER_ALERT: u16 = 0;
+
+/// Decode a Maximum Response Code into milliseconds.
+pub fn maximum_response_delay(code: u16) -> u32 {
+	if code < 0x8000 {
+		u32::from(code)
+	}
+	else {
+		let exponent = (code >> 12) & 0x07;
+		let mantissa = code & 0x0fff;
+
+		u32::from(mantissa | 0x1000) << (exponent + 3)
+	}
+}
+
+/// Decode a Querier's Query Interval Code into seconds.
+pub fn query_interval(code: u8) -> u32 {
+	if code < 0x80 {
+		u32::from(code)
+	}
+	else {
+		let exponent = (code >> 4) & 0x07;
+		let mantissa = code & 0x0f;
+
+		u32::from(mantissa | 0x10) << (exponent + 3)
+	}
+}
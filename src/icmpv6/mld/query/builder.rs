@@ -0,0 +1,212 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::Kind;
+use crate::icmpv6::mld::query::Packet;
+
+/// Multicast Listener Query packet builder.
+///
+/// The query is an MLDv1 one unless any of the MLDv2 fields is set.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+
+	sources: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(24)?;
+		buffer.data_mut()[0] = Kind::MulticastListenerQuery.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+
+			sources: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Maximum Response Code.
+	pub fn maximum_response_code(mut self, value: u16) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_maximum_response_code(value)?;
+		Ok(self)
+	}
+
+	/// Multicast address being queried, leave it unset for a General Query.
+	pub fn address(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_address(value)?;
+		Ok(self)
+	}
+
+	/// Make it an MLDv2 query.
+	pub fn v2(mut self) -> Result<Self> {
+		if self.buffer.length() == 24 {
+			self.buffer.more(4)?;
+		}
+
+		Ok(self)
+	}
+
+	/// Suppress Router-Side Processing flag, makes it an MLDv2 query.
+	pub fn suppress(mut self, value: bool) -> Result<Self> {
+		self = self.v2()?;
+		Packet::unchecked(self.buffer.data_mut()).set_suppress(value)?;
+		Ok(self)
+	}
+
+	/// Querier's Robustness Variable, makes it an MLDv2 query.
+	pub fn robustness(mut self, value: u8) -> Result<Self> {
+		self = self.v2()?;
+		Packet::unchecked(self.buffer.data_mut()).set_robustness(value)?;
+		Ok(self)
+	}
+
+	/// Querier's Query Interval Code, makes it an MLDv2 query.
+	pub fn query_interval_code(mut self, value: u8) -> Result<Self> {
+		self = self.v2()?;
+		Packet::unchecked(self.buffer.data_mut()).set_query_interval_code(value)?;
+		Ok(self)
+	}
+
+	/// Source addresses for a Multicast Address and Source Specific Query,
+	/// makes it an MLDv2 query.
+	pub fn sources<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(mut self, value: T) -> Result<Self> {
+		if self.sources {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self = self.v2()?;
+		self.sources = true;
+
+		let mut count = 0usize;
+
+		for source in value {
+			let offset = self.buffer.length();
+			self.buffer.more(16)?;
+			self.buffer.data_mut()[offset ..].copy_from_slice(&source.octets());
+
+			count += 1;
+		}
+
+		if count > u16::max_value() as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		Cursor::new(&mut self.buffer.data_mut()[26 ..])
+			.write_u16::<BigEndian>(count as u16)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn v1() {
+		let packet = ip::v6::Builder::default()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("ff02::1".parse().unwrap()).unwrap()
+			.mld().unwrap().mld_query().unwrap()
+				.maximum_response_code(10000).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		let icmp = icmpv6::Packet::new(ip.upper_layer().unwrap().1).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let query = icmp.mld_query().unwrap();
+		assert!(!query.is_v2());
+		assert!(query.is_general());
+		assert_eq!(query.maximum_response_delay(), 10000);
+		assert_eq!(query.sources().count(), 0);
+	}
+
+	#[test]
+	fn v2() {
+		let packet = ip::v6::Builder::default()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("ff3e::1234".parse().unwrap()).unwrap()
+			.mld().unwrap().mld_query().unwrap()
+				.maximum_response_code(0x8c40).unwrap()
+				.address("ff3e::1234".parse().unwrap()).unwrap()
+				.suppress(true).unwrap()
+				.robustness(2).unwrap()
+				.query_interval_code(0x8e).unwrap()
+				.sources(&["2001:db8::1".parse().unwrap()]).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		let icmp = icmpv6::Packet::new(ip.upper_layer().unwrap().1).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let query = icmp.mld_query().unwrap();
+		assert!(query.is_v2());
+		assert!(!query.is_general());
+		assert_eq!(query.address(), "ff3e::1234".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(query.maximum_response_delay(), 0x1c40 << 3);
+		assert!(query.suppress());
+		assert_eq!(query.robustness(), 2);
+		assert_eq!(query.query_interval(), 0x1e << 3);
+		assert_eq!(query.sources().collect::<Vec<_>>(), vec!["2001:db8::1".parse::<Ipv6Addr>().unwrap()]);
+	}
+}
@@ -0,0 +1,302 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::icmpv6::Kind;
+use crate::icmpv6::mld;
+
+/// Multicast Listener Query packet parser.
+///
+/// # Note
+///
+/// MLDv2 queries are told apart from MLDv1 ones by their length, so the
+/// buffer must not contain anything past the ICMPv6 message.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  24,
+		max:  28,
+		size: p => if p.is_v2() { 28 } else { 24 },
+	}
+
+	payload {
+		min:  0,
+		size: p => if p.is_v2() { p.number_of_sources() as usize * 16 } else { 0 },
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::mld::query::Packet")
+			.field("maximum_response_code", &self.maximum_response_code())
+			.field("address", &self.address())
+			.field("is_v2", &self.is_v2())
+			.field("sources", &self.sources().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Multicast Listener Query packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Multicast Listener Query packet, checking the buffer contents
+	/// are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if Kind::from(packet.buffer.as_ref()[0]) != Kind::MulticastListenerQuery {
+			Err(Error::InvalidPacket)?
+		}
+
+		// Anything between the two versions is invalid.
+		if packet.buffer.as_ref().len() > 24 && packet.buffer.as_ref().len() < 28 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < packet.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		let header = if self.is_v2() { 28 } else { 24 };
+		let size   = self.size();
+
+		self.buffer.as_ref()[.. size].split_at(header)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let header = if self.is_v2() { 28 } else { 24 };
+		let size   = self.size();
+
+		self.buffer.as_mut()[.. size].split_at_mut(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Whether the query is an MLDv2 one.
+	pub fn is_v2(&self) -> bool {
+		self.buffer.as_ref().len() >= 28
+	}
+
+	/// Maximum Response Code, for MLDv1 queries it's the delay in
+	/// milliseconds.
+	pub fn maximum_response_code(&self) -> u16 {
+		(&self.buffer.as_ref()[4 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Maximum response delay in milliseconds.
+	pub fn maximum_response_delay(&self) -> u32 {
+		if self.is_v2() {
+			mld::maximum_response_delay(self.maximum_response_code())
+		}
+		else {
+			u32::from(self.maximum_response_code())
+		}
+	}
+
+	/// Multicast address being queried, unspecified for a General Query.
+	pub fn address(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[8 .. 24]);
+
+		Ipv6Addr::from(octets)
+	}
+
+	/// Whether the query is a General Query.
+	pub fn is_general(&self) -> bool {
+		self.address().is_unspecified()
+	}
+
+	/// Whether routers must suppress timer updates on receipt (MLDv2 only).
+	pub fn suppress(&self) -> bool {
+		self.is_v2() && self.buffer.as_ref()[24] & 0x08 != 0
+	}
+
+	/// Querier's Robustness Variable (MLDv2 only).
+	pub fn robustness(&self) -> u8 {
+		if self.is_v2() {
+			self.buffer.as_ref()[24] & 0x07
+		}
+		else {
+			0
+		}
+	}
+
+	/// Querier's Query Interval Code (MLDv2 only).
+	pub fn query_interval_code(&self) -> u8 {
+		if self.is_v2() {
+			self.buffer.as_ref()[25]
+		}
+		else {
+			0
+		}
+	}
+
+	/// Querier's query interval in seconds (MLDv2 only).
+	pub fn query_interval(&self) -> u32 {
+		mld::query_interval(self.query_interval_code())
+	}
+
+	/// Number of sources (MLDv2 only).
+	pub fn number_of_sources(&self) -> u16 {
+		if self.is_v2() {
+			(&self.buffer.as_ref()[26 ..]).read_u16::<BigEndian>().unwrap()
+		}
+		else {
+			0
+		}
+	}
+
+	/// Source addresses of a Multicast Address and Source Specific Query.
+	pub fn sources(&self) -> impl Iterator<Item = Ipv6Addr> + '_ {
+		self.payload().chunks(16).map(|chunk| {
+			let mut octets = [0u8; 16];
+			octets.copy_from_slice(chunk);
+
+			Ipv6Addr::from(octets)
+		})
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Maximum Response Code.
+	pub fn set_maximum_response_code(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[4 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Multicast address being queried.
+	pub fn set_address(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[8 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Suppress Router-Side Processing flag (MLDv2 only).
+	pub fn set_suppress(&mut self, value: bool) -> Result<&mut Self> {
+		if !self.is_v2() {
+			Err(Error::InvalidPacket)?
+		}
+
+		let buffer = self.buffer.as_mut();
+
+		if value {
+			buffer[24] |= 0x08;
+		}
+		else {
+			buffer[24] &= !0x08;
+		}
+
+		Ok(self)
+	}
+
+	/// Querier's Robustness Variable (MLDv2 only).
+	pub fn set_robustness(&mut self, value: u8) -> Result<&mut Self> {
+		if !self.is_v2() {
+			Err(Error::InvalidPacket)?
+		}
+
+		if value > 7 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[24] = (buffer[24] & !0x07) | value;
+
+		Ok(self)
+	}
+
+	/// Querier's Query Interval Code (MLDv2 only).
+	pub fn set_query_interval_code(&mut self, value: u8) -> Result<&mut Self> {
+		if !self.is_v2() {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.buffer.as_mut()[25] = value;
+
+		Ok(self)
+	}
+}
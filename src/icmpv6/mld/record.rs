@@ -0,0 +1,260 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+
+/// Multicast address record parser.
+pub struct Record<B> {
+	buffer: B,
+}
+
+sized!(Record,
+	header {
+		min:  20,
+		max:  20,
+		size: 20,
+	}
+
+	payload {
+		min:  0,
+		max:  u16::max_value() as usize * 16 + u8::max_value() as usize * 4,
+		size: p => p.number_of_sources() as usize * 16 + p.auxiliary_length() as usize * 4,
+	});
+
+/// Multicast address record types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+	///
+	ModeIsInclude,
+
+	///
+	ModeIsExclude,
+
+	///
+	ChangeToInclude,
+
+	///
+	ChangeToExclude,
+
+	///
+	AllowNewSources,
+
+	///
+	BlockOldSources,
+
+	///
+	Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Record<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::mld::Record")
+			.field("kind", &self.kind())
+			.field("address", &self.address())
+			.field("sources", &self.sources().collect::<Vec<_>>())
+			.field("auxiliary", &self.auxiliary())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Record<B> {
+	/// Create a record without checking the buffer.
+	pub fn unchecked(buffer: B) -> Record<B> {
+		Record { buffer }
+	}
+
+	/// Parse a record, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Record<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let record = Record::unchecked(buffer);
+
+		if record.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if record.buffer.as_ref().len() < record.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(record)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Record<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Record<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Record<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Record<&[u8]>> {
+		Record::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Record<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Record<&mut [u8]>> {
+		Record::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Record<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(20)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Record<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(20)
+	}
+}
+
+impl<B: AsRef<[u8]>> Record<B> {
+	/// Record type.
+	pub fn kind(&self) -> Kind {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the auxiliary data in 32-bit words.
+	pub fn auxiliary_length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Number of source addresses.
+	pub fn number_of_sources(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Multicast address the record pertains to.
+	pub fn address(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[4 .. 20]);
+
+		Ipv6Addr::from(octets)
+	}
+
+	/// Source addresses.
+	pub fn sources(&self) -> impl Iterator<Item = Ipv6Addr> + '_ {
+		let length = self.number_of_sources() as usize * 16;
+
+		self.payload()[.. length].chunks(16).map(|chunk| {
+			let mut octets = [0u8; 16];
+			octets.copy_from_slice(chunk);
+
+			Ipv6Addr::from(octets)
+		})
+	}
+
+	/// Auxiliary data.
+	pub fn auxiliary(&self) -> &[u8] {
+		&self.payload()[self.number_of_sources() as usize * 16 ..]
+	}
+}
+
+/// Iterator over multicast address records.
+pub struct RecordIter<'a> {
+	buffer: &'a [u8],
+	count:  u16,
+}
+
+impl<'a> RecordIter<'a> {
+	pub(in crate::icmpv6::mld) fn new(buffer: &'a [u8], count: u16) -> Self {
+		RecordIter {
+			buffer: buffer,
+			count:  count,
+		}
+	}
+}
+
+impl<'a> Iterator for RecordIter<'a> {
+	type Item = Result<Record<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use crate::size::Size;
+
+		if self.count == 0 {
+			return None;
+		}
+
+		self.count -= 1;
+
+		match Record::new(self.buffer) {
+			Ok(record) => {
+				self.buffer = &self.buffer[record.size() ..];
+				Some(Ok(record))
+			}
+
+			Err(error) => {
+				self.count = 0;
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+impl From<u8> for Kind {
+	fn from(value: u8) -> Kind {
+		use self::Kind::*;
+
+		match value {
+			1 => ModeIsInclude,
+			2 => ModeIsExclude,
+			3 => ChangeToInclude,
+			4 => ChangeToExclude,
+			5 => AllowNewSources,
+			6 => BlockOldSources,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Kind {
+	fn into(self) -> u8 {
+		use self::Kind::*;
+
+		match self {
+			ModeIsInclude   => 1,
+			ModeIsExclude   => 2,
+			ChangeToInclude => 3,
+			ChangeToExclude => 4,
+			AllowNewSources => 5,
+			BlockOldSources => 6,
+			Unknown(n)      => n,
+		}
+	}
+}
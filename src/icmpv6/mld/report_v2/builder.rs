@@ -0,0 +1,162 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::Kind;
+use crate::icmpv6::mld::record;
+use crate::icmpv6::mld::report_v2::Packet;
+
+/// Version 2 Multicast Listener Report packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+
+	records: u16,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::MulticastListenerReportV2.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+
+			records: 0,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Append a multicast address record.
+	pub fn record<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(mut self, kind: record::Kind, address: Ipv6Addr, sources: T) -> Result<Self> {
+		if self.records == u16::max_value() {
+			Err(Error::InvalidPacket)?
+		}
+
+		let start = self.buffer.length();
+		self.buffer.more(20)?;
+
+		let mut count = 0usize;
+
+		for source in sources {
+			let offset = self.buffer.length();
+			self.buffer.more(16)?;
+			self.buffer.data_mut()[offset ..].copy_from_slice(&source.octets());
+
+			count += 1;
+		}
+
+		if count > u16::max_value() as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		{
+			let data = &mut self.buffer.data_mut()[start ..];
+			data[0] = kind.into();
+			data[2 .. 4].copy_from_slice(&(count as u16).to_be_bytes());
+			data[4 .. 20].copy_from_slice(&address.octets());
+		}
+
+		self.records += 1;
+		Packet::unchecked(self.buffer.data_mut()).set_number_of_records(self.records)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmpv6;
+	use crate::icmpv6::mld;
+
+	#[test]
+	fn records() {
+		let sources = ["2001:db8::1".parse().unwrap(), "2001:db8::2".parse().unwrap()];
+
+		let packet = ip::v6::Builder::default()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("ff02::16".parse().unwrap()).unwrap()
+			.mld().unwrap().mld_report_v2().unwrap()
+				.record(mld::record::Kind::ModeIsInclude, "ff3e::1234".parse().unwrap(), &sources).unwrap()
+				.record(mld::record::Kind::ChangeToExclude, "ff05::2".parse().unwrap(), &[]).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.hop_limit(), 1);
+		assert_eq!(ip.router_alert(), Some(mld::ROUTER_ALERT));
+
+		let (protocol, payload) = ip.upper_layer().unwrap();
+		assert_eq!(protocol, ip::Protocol::Ipv6Icmp);
+
+		let icmp = icmpv6::Packet::new(payload).unwrap();
+		assert_eq!(icmp.kind(), icmpv6::Kind::MulticastListenerReportV2);
+		assert!(icmp.is_valid(&ip));
+
+		let report = icmp.mld_report_v2().unwrap();
+		assert_eq!(report.number_of_records(), 2);
+
+		let records = report.records().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(records[0].kind(), mld::record::Kind::ModeIsInclude);
+		assert_eq!(records[0].address(), "ff3e::1234".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(records[0].sources().collect::<Vec<_>>(), sources);
+		assert_eq!(records[0].auxiliary(), &[]);
+
+		assert_eq!(records[1].kind(), mld::record::Kind::ChangeToExclude);
+		assert_eq!(records[1].number_of_sources(), 0);
+		assert_eq!(records[1].address(), "ff05::2".parse::<Ipv6Addr>().unwrap());
+	}
+}
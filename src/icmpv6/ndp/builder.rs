@@ -0,0 +1,150 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{WriteBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::Buffer;
+use crate::icmpv6::ndp::Preference;
+use crate::icmpv6::ndp::flag::PrefixFlags;
+use crate::icmpv6::ndp::option::Kind;
+
+/// Append an option, padding it to a multiple of 8 octets.
+pub(in crate::icmpv6::ndp) fn option<B: Buffer>(buffer: &mut B, kind: Kind, value: &[u8]) -> Result<()> {
+	let length = (2 + value.len() + 7) / 8;
+
+	if length > u8::max_value() as usize {
+		Err(Error::InvalidValue)?
+	}
+
+	let offset = buffer.length();
+	buffer.more(length * 8)?;
+
+	let data = &mut buffer.data_mut()[offset ..];
+	data[0] = kind.into();
+	data[1] = length as u8;
+	data[2 .. 2 + value.len()].copy_from_slice(value);
+
+	Ok(())
+}
+
+/// Append a Source or Target Link-Layer Address option.
+pub(in crate::icmpv6::ndp) fn link_layer_address<B: Buffer>(buffer: &mut B, kind: Kind, value: HwAddr) -> Result<()> {
+	option(buffer, kind, &value.octets())
+}
+
+/// Append an MTU option.
+pub(in crate::icmpv6::ndp) fn mtu<B: Buffer>(buffer: &mut B, value: u32) -> Result<()> {
+	let mut data = [0u8; 6];
+	Cursor::new(&mut data[2 ..]).write_u32::<BigEndian>(value)?;
+
+	option(buffer, Kind::Mtu, &data)
+}
+
+/// Append a Prefix Information option.
+pub(in crate::icmpv6::ndp) fn prefix_information<B: Buffer>(buffer: &mut B, prefix: Ipv6Addr, length: u8, flags: PrefixFlags, valid: u32, preferred: u32) -> Result<()> {
+	if length > 128 {
+		Err(Error::InvalidValue)?
+	}
+
+	let mut data = [0u8; 30];
+	data[0] = length;
+	data[1] = flags.bits();
+	Cursor::new(&mut data[2 ..]).write_u32::<BigEndian>(valid)?;
+	Cursor::new(&mut data[6 ..]).write_u32::<BigEndian>(preferred)?;
+	data[14 ..].copy_from_slice(&prefix.octets());
+
+	option(buffer, Kind::PrefixInformation, &data)
+}
+
+/// Append a Route Information option, carrying only the prefix octets that
+/// are needed.
+pub(in crate::icmpv6::ndp) fn route_information<B: Buffer>(buffer: &mut B, prefix: Ipv6Addr, length: u8, preference: Preference, lifetime: u32) -> Result<()> {
+	if length > 128 {
+		Err(Error::InvalidValue)?
+	}
+
+	let octets = match length {
+		0        => 0,
+		1 ..= 64 => 8,
+		_        => 16,
+	};
+
+	let mut data = [0u8; 22];
+	data[0] = length;
+	data[1] = Into::<u8>::into(preference) << 3;
+	Cursor::new(&mut data[2 ..]).write_u32::<BigEndian>(lifetime)?;
+	data[6 .. 6 + octets].copy_from_slice(&prefix.octets()[.. octets]);
+
+	option(buffer, Kind::RouteInformation, &data[.. 6 + octets])
+}
+
+/// Append a Recursive DNS Server option.
+pub(in crate::icmpv6::ndp) fn recursive_dns_server<B: Buffer>(buffer: &mut B, lifetime: u32, servers: &[Ipv6Addr]) -> Result<()> {
+	if servers.is_empty() {
+		Err(Error::InvalidValue)?
+	}
+
+	let mut data = vec![0u8; 6];
+	Cursor::new(&mut data[2 ..]).write_u32::<BigEndian>(lifetime)?;
+
+	for server in servers {
+		data.extend_from_slice(&server.octets());
+	}
+
+	option(buffer, Kind::RecursiveDnsServer, &data)
+}
+
+/// Append a DNS Search List option, encoding the domain names as DNS labels.
+pub(in crate::icmpv6::ndp) fn dns_search_list<B: Buffer>(buffer: &mut B, lifetime: u32, domains: &[&str]) -> Result<()> {
+	if domains.is_empty() {
+		Err(Error::InvalidValue)?
+	}
+
+	let mut data = vec![0u8; 6];
+	Cursor::new(&mut data[2 ..]).write_u32::<BigEndian>(lifetime)?;
+
+	for domain in domains {
+		for label in domain.trim_end_matches('.').split('.') {
+			if label.is_empty() || label.len() > 63 {
+				Err(Error::InvalidValue)?
+			}
+
+			data.push(label.len() as u8);
+			data.extend_from_slice(label.as_bytes());
+		}
+
+		data.push(0);
+	}
+
+	option(buffer, Kind::DnsSearchList, &data)
+}
+
+/// Append a Redirected Header option, truncated so the message fits in the
+/// minimum IPv6 MTU.
+pub(in crate::icmpv6::ndp) fn redirected_header<B: Buffer>(buffer: &mut B, ip: (usize, usize), value: &[u8]) -> Result<()> {
+	use crate::icmpv6::builder::MINIMUM_MTU;
+
+	let used      = buffer.offset() - ip.0 + buffer.length();
+	let available = (MINIMUM_MTU.saturating_sub(used + 8)) / 8 * 8;
+	let length    = value.len().min(available);
+
+	let mut data = vec![0u8; 6];
+	data.extend_from_slice(&value[.. length]);
+
+	option(buffer, Kind::RedirectedHeader, &data)
+}
@@ -0,0 +1,54 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use bitflags::bitflags;
+
+bitflags! {
+	/// Router Advertisement flags.
+	pub struct RouterFlags: u8 {
+		/// Addresses are available via DHCPv6.
+		const MANAGED = 0b1000_0000;
+
+		/// Other configuration is available via DHCPv6.
+		const OTHER = 0b0100_0000;
+
+		/// The router is a Mobile IPv6 home agent.
+		const HOME_AGENT = 0b0010_0000;
+	}
+}
+
+bitflags! {
+	/// Neighbor Advertisement flags.
+	pub struct NeighborFlags: u8 {
+		/// The sender is a router.
+		const ROUTER = 0b1000_0000;
+
+		/// The advertisement was sent in response to a solicitation.
+		const SOLICITED = 0b0100_0000;
+
+		/// The advertisement should override an existing cache entry.
+		const OVERRIDE = 0b0010_0000;
+	}
+}
+
+bitflags! {
+	/// Prefix Information flags.
+	pub struct PrefixFlags: u8 {
+		/// The prefix can be used for on-link determination.
+		const ON_LINK = 0b1000_0000;
+
+		/// The prefix can be used for stateless address configuration.
+		const AUTONOMOUS = 0b0100_0000;
+	}
+}
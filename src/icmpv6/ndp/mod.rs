@@ -0,0 +1,40 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// NDP flags.
+pub mod flag;
+
+mod preference;
+pub use self::preference::Preference;
+
+/// NDP options.
+pub mod option;
+pub use self::option::Option;
+
+mod builder;
+
+/// Router Solicitation.
+pub mod router_solicitation;
+
+/// Router Advertisement.
+pub mod router_advertisement;
+
+/// Neighbor Solicitation.
+pub mod neighbor_solicitation;
+
+/// Neighbor Advertisement.
+pub mod neighbor_advertisement;
+
+/// Redirect.
+pub mod redirect;
@@ -0,0 +1,134 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::Kind;
+use crate::icmpv6::ndp::builder as option;
+use crate::icmpv6::ndp::option::Kind as OptionKind;
+use crate::icmpv6::ndp::neighbor_solicitation::Packet;
+
+/// Neighbor Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(24)?;
+		buffer.data_mut()[0] = Kind::NeighborSolicitation.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Target address.
+	pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_target(value)?;
+		Ok(self)
+	}
+
+	/// Append a Source Link-Layer Address option.
+	pub fn source_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		option::link_layer_address(&mut self.buffer, OptionKind::SourceLinkLayerAddress, value)?;
+		Ok(self)
+	}
+
+	/// Append a raw option, padded to a multiple of 8 octets.
+	pub fn option(mut self, kind: OptionKind, value: &[u8]) -> Result<Self> {
+		option::option(&mut self.buffer, kind, value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn ether() {
+		let packet = ether::Builder::default()
+			.destination("33:33:ff:00:00:02".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.ip().unwrap().v6().unwrap()
+				.hop_limit(255).unwrap()
+				.source("fe80::1".parse().unwrap()).unwrap()
+				.destination("ff02::1:ff00:2".parse().unwrap()).unwrap()
+				.icmp().unwrap().neighbor_solicitation().unwrap()
+					.target("fe80::2".parse().unwrap()).unwrap()
+					.source_link_layer_address("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+					.build().unwrap();
+
+		let ether = ether::Packet::new(packet).unwrap();
+		let ip    = ip::v6::Packet::new(ether.payload()).unwrap();
+		assert_eq!(ip.payload_length(), 32);
+
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let ns = icmp.neighbor_solicitation().unwrap();
+		assert_eq!(ns.target(), "fe80::2".parse::<Ipv6Addr>().unwrap());
+
+		let option = ns.options().next().unwrap().unwrap();
+		assert_eq!(option.link_layer_address().unwrap(), ether.source());
+	}
+}
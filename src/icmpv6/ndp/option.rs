@@ -0,0 +1,561 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmpv6::ndp::Preference;
+use crate::icmpv6::ndp::flag::PrefixFlags;
+
+/// NDP option parser.
+pub struct Option<B> {
+	buffer: B,
+}
+
+sized!(Option,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  6,
+		max:  2038,
+		size: p => (p.length() as usize * 8).saturating_sub(2),
+	});
+
+/// NDP option types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+	///
+	SourceLinkLayerAddress,
+
+	///
+	TargetLinkLayerAddress,
+
+	///
+	PrefixInformation,
+
+	///
+	RedirectedHeader,
+
+	///
+	Mtu,
+
+	///
+	RouteInformation,
+
+	///
+	RecursiveDnsServer,
+
+	///
+	DnsSearchList,
+
+	///
+	Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::ndp::Option")
+			.field("kind", &self.kind())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Create an NDP option without checking the buffer.
+	pub fn unchecked(buffer: B) -> Option<B> {
+		Option { buffer }
+	}
+
+	/// Parse an NDP option, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Option<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let option = Option::unchecked(buffer);
+
+		if option.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		// Nodes must silently discard packets with zero length options.
+		if option.length() == 0 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if option.buffer.as_ref().len() < option.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(option)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Option<&[u8]>> {
+		Option::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+		Option::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(2)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(2)
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Option type.
+	pub fn kind(&self) -> Kind {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the option in 8 octet units, including the type and length.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Link-layer address of a Source or Target Link-Layer Address option.
+	pub fn link_layer_address(&self) -> Result<HwAddr> {
+		match self.kind() {
+			Kind::SourceLinkLayerAddress |
+			Kind::TargetLinkLayerAddress =>
+				Ok(self.payload()[.. 6].into()),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+
+	/// MTU of an MTU option.
+	pub fn mtu(&self) -> Result<u32> {
+		if self.kind() != Kind::Mtu {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((&self.payload()[2 ..]).read_u32::<BigEndian>()?)
+	}
+
+	/// Parse a Prefix Information option.
+	pub fn prefix_information(&self) -> Result<PrefixInformation<&[u8]>> {
+		if self.kind() != Kind::PrefixInformation {
+			Err(Error::InvalidPacket)?
+		}
+
+		PrefixInformation::new(self.payload())
+	}
+
+	/// Parse a Route Information option.
+	pub fn route_information(&self) -> Result<RouteInformation<&[u8]>> {
+		if self.kind() != Kind::RouteInformation {
+			Err(Error::InvalidPacket)?
+		}
+
+		RouteInformation::new(self.payload())
+	}
+
+	/// Parse a Recursive DNS Server option.
+	pub fn recursive_dns_server(&self) -> Result<RecursiveDnsServer<&[u8]>> {
+		if self.kind() != Kind::RecursiveDnsServer {
+			Err(Error::InvalidPacket)?
+		}
+
+		RecursiveDnsServer::new(self.payload())
+	}
+
+	/// Parse a DNS Search List option.
+	pub fn dns_search_list(&self) -> Result<DnsSearchList<&[u8]>> {
+		if self.kind() != Kind::DnsSearchList {
+			Err(Error::InvalidPacket)?
+		}
+
+		DnsSearchList::new(self.payload())
+	}
+
+	/// Packet contained in a Redirected Header option.
+	///
+	/// # Note
+	///
+	/// The packet is usually truncated, so its payload isn't checked.
+	pub fn redirected_header(&self) -> Result<ip::v6::Packet<&[u8]>> {
+		if self.kind() != Kind::RedirectedHeader {
+			Err(Error::InvalidPacket)?
+		}
+
+		ip::v6::Packet::no_payload(&self.payload()[6 ..])
+	}
+}
+
+/// Iterator over NDP options.
+pub struct OptionIter<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> OptionIter<'a> {
+	pub(in crate::icmpv6::ndp) fn new(buffer: &'a [u8]) -> Self {
+		OptionIter {
+			buffer: buffer,
+		}
+	}
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+	type Item = Result<Option<&'a [u8]>>;
+
+	fn next(&mut self) -> std::option::Option<Self::Item> {
+		use crate::size::Size;
+
+		if self.buffer.is_empty() {
+			return None;
+		}
+
+		match Option::new(self.buffer) {
+			Ok(option) => {
+				self.buffer = &self.buffer[option.size() ..];
+				Some(Ok(option))
+			}
+
+			Err(error) => {
+				self.buffer = &[];
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+/// Prefix Information option parser.
+pub struct PrefixInformation<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for PrefixInformation<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::ndp::option::PrefixInformation")
+			.field("prefix", &self.prefix())
+			.field("prefix_length", &self.prefix_length())
+			.field("flags", &self.flags())
+			.field("valid_lifetime", &self.valid_lifetime())
+			.field("preferred_lifetime", &self.preferred_lifetime())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> PrefixInformation<B> {
+	/// Parse a Prefix Information option payload.
+	pub fn new(buffer: B) -> Result<PrefixInformation<B>> {
+		if buffer.as_ref().len() < 30 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(PrefixInformation { buffer })
+	}
+
+	/// Number of leading bits in the prefix that are valid.
+	pub fn prefix_length(&self) -> u8 {
+		self.buffer.as_ref()[0]
+	}
+
+	/// Prefix flags.
+	pub fn flags(&self) -> PrefixFlags {
+		PrefixFlags::from_bits_truncate(self.buffer.as_ref()[1])
+	}
+
+	/// Seconds the prefix is valid for on-link determination.
+	pub fn valid_lifetime(&self) -> u32 {
+		(&self.buffer.as_ref()[2 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Seconds addresses generated from the prefix remain preferred.
+	pub fn preferred_lifetime(&self) -> u32 {
+		(&self.buffer.as_ref()[6 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// The advertised prefix.
+	pub fn prefix(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[14 .. 30]);
+
+		Ipv6Addr::from(octets)
+	}
+}
+
+/// Route Information option parser.
+pub struct RouteInformation<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for RouteInformation<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::ndp::option::RouteInformation")
+			.field("prefix", &self.prefix())
+			.field("prefix_length", &self.prefix_length())
+			.field("preference", &self.preference())
+			.field("lifetime", &self.lifetime())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> RouteInformation<B> {
+	/// Parse a Route Information option payload.
+	pub fn new(buffer: B) -> Result<RouteInformation<B>> {
+		if buffer.as_ref().len() < 6 {
+			Err(Error::SmallBuffer)?
+		}
+
+		if buffer.as_ref()[0] > 128 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(RouteInformation { buffer })
+	}
+
+	/// Number of leading bits in the prefix that are valid.
+	pub fn prefix_length(&self) -> u8 {
+		self.buffer.as_ref()[0]
+	}
+
+	/// Preference of the route.
+	pub fn preference(&self) -> Preference {
+		(self.buffer.as_ref()[1] >> 3).into()
+	}
+
+	/// Seconds the route is valid for.
+	pub fn lifetime(&self) -> u32 {
+		(&self.buffer.as_ref()[2 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// The route prefix, the bits not carried in the option are zero.
+	pub fn prefix(&self) -> Ipv6Addr {
+		let prefix = &self.buffer.as_ref()[6 ..];
+		let length = prefix.len().min(16);
+
+		let mut octets = [0u8; 16];
+		octets[.. length].copy_from_slice(&prefix[.. length]);
+
+		Ipv6Addr::from(octets)
+	}
+}
+
+/// Recursive DNS Server option parser.
+pub struct RecursiveDnsServer<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for RecursiveDnsServer<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::ndp::option::RecursiveDnsServer")
+			.field("lifetime", &self.lifetime())
+			.field("servers", &self.servers().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> RecursiveDnsServer<B> {
+	/// Parse a Recursive DNS Server option payload.
+	pub fn new(buffer: B) -> Result<RecursiveDnsServer<B>> {
+		if buffer.as_ref().len() < 22 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(RecursiveDnsServer { buffer })
+	}
+
+	/// Seconds the servers can be used for.
+	pub fn lifetime(&self) -> u32 {
+		(&self.buffer.as_ref()[2 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Addresses of the servers.
+	pub fn servers(&self) -> impl Iterator<Item = Ipv6Addr> + '_ {
+		self.buffer.as_ref()[6 ..].chunks(16).filter(|c| c.len() == 16).map(|chunk| {
+			let mut octets = [0u8; 16];
+			octets.copy_from_slice(chunk);
+
+			Ipv6Addr::from(octets)
+		})
+	}
+}
+
+/// DNS Search List option parser.
+pub struct DnsSearchList<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for DnsSearchList<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::ndp::option::DnsSearchList")
+			.field("lifetime", &self.lifetime())
+			.field("domains", &self.domains())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> DnsSearchList<B> {
+	/// Parse a DNS Search List option payload.
+	pub fn new(buffer: B) -> Result<DnsSearchList<B>> {
+		if buffer.as_ref().len() < 14 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(DnsSearchList { buffer })
+	}
+
+	/// Seconds the domains can be used for.
+	pub fn lifetime(&self) -> u32 {
+		(&self.buffer.as_ref()[2 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Domain names in the search list.
+	pub fn domains(&self) -> Result<Vec<String>> {
+		let mut buffer  = &self.buffer.as_ref()[6 ..];
+		let mut domains = Vec::new();
+		let mut labels  = Vec::new();
+
+		while let Some((&length, rest)) = buffer.split_first() {
+			let length = length as usize;
+
+			if length == 0 {
+				// Trailing zeroes are padding.
+				if labels.is_empty() {
+					break;
+				}
+
+				domains.push(labels.join("."));
+				labels.clear();
+			}
+			else {
+				if rest.len() < length || length > 63 {
+					Err(Error::InvalidPacket)?
+				}
+
+				labels.push(String::from_utf8_lossy(&rest[.. length]).into_owned());
+			}
+
+			buffer = &rest[length ..];
+		}
+
+		if !labels.is_empty() {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(domains)
+	}
+}
+
+impl From<u8> for Kind {
+	fn from(value: u8) -> Self {
+		use self::Kind::*;
+
+		match value {
+			1  => SourceLinkLayerAddress,
+			2  => TargetLinkLayerAddress,
+			3  => PrefixInformation,
+			4  => RedirectedHeader,
+			5  => Mtu,
+			24 => RouteInformation,
+			25 => RecursiveDnsServer,
+			31 => DnsSearchList,
+			v  => Unknown(v),
+		}
+	}
+}
+
+impl Into<u8> for Kind {
+	fn into(self) -> u8 {
+		use self::Kind::*;
+
+		match self {
+			SourceLinkLayerAddress => 1,
+			TargetLinkLayerAddress => 2,
+			PrefixInformation      => 3,
+			RedirectedHeader       => 4,
+			Mtu                    => 5,
+			RouteInformation       => 24,
+			RecursiveDnsServer     => 25,
+			DnsSearchList          => 31,
+			Unknown(v)             => v,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::icmpv6::ndp::option;
+
+	#[test]
+	fn zero_length() {
+		assert!(option::Option::new(&[0x01, 0x00, 0, 0, 0, 0, 0, 0][..]).is_err());
+	}
+
+	#[test]
+	fn short() {
+		assert!(option::Option::new(&[0x01, 0x02, 0, 0, 0, 0, 0, 0][..]).is_err());
+	}
+
+	#[test]
+	fn dns_search_list() {
+		let raw = [
+			0x1f, 0x03, 0x00, 0x00, 0x00, 0x00, 0x0e, 0x10,
+			0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+			0x03, b'c', b'o', b'm', 0x00, 0x00, 0x00, 0x00];
+
+		let option = option::Option::new(&raw[..]).unwrap();
+		let dnssl  = option.dns_search_list().unwrap();
+		assert_eq!(dnssl.lifetime(), 3600);
+		assert_eq!(dnssl.domains().unwrap(), vec!["example.com"]);
+	}
+}
@@ -0,0 +1,55 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Default router and route preference.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Preference {
+	///
+	High,
+
+	///
+	Medium,
+
+	///
+	Low,
+
+	///
+	Reserved,
+}
+
+impl From<u8> for Preference {
+	fn from(value: u8) -> Self {
+		use self::Preference::*;
+
+		match value & 0b11 {
+			0b01 => High,
+			0b00 => Medium,
+			0b11 => Low,
+			_    => Reserved,
+		}
+	}
+}
+
+impl Into<u8> for Preference {
+	fn into(self) -> u8 {
+		use self::Preference::*;
+
+		match self {
+			High     => 0b01,
+			Medium   => 0b00,
+			Low      => 0b11,
+			Reserved => 0b10,
+		}
+	}
+}
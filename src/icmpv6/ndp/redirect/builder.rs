@@ -0,0 +1,153 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::Kind;
+use crate::icmpv6::ndp::builder as option;
+use crate::icmpv6::ndp::option::Kind as OptionKind;
+use crate::icmpv6::ndp::redirect::Packet;
+
+/// Redirect packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(40)?;
+		buffer.data_mut()[0] = Kind::Redirect.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Better first-hop address for the destination.
+	pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_target(value)?;
+		Ok(self)
+	}
+
+	/// Destination address that is redirected to the target.
+	pub fn destination(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
+		Ok(self)
+	}
+
+	/// Append a Target Link-Layer Address option.
+	pub fn target_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		option::link_layer_address(&mut self.buffer, OptionKind::TargetLinkLayerAddress, value)?;
+		Ok(self)
+	}
+
+	/// Append a Redirected Header option with the packet that triggered the
+	/// redirect, truncated to fit the minimum IPv6 MTU.
+	pub fn redirected_header(mut self, value: &[u8]) -> Result<Self> {
+		option::redirected_header(&mut self.buffer, self.ip, value)?;
+		Ok(self)
+	}
+
+	/// Append a raw option, padded to a multiple of 8 octets.
+	pub fn option(mut self, kind: OptionKind, value: &[u8]) -> Result<Self> {
+		option::option(&mut self.buffer, kind, value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn redirected_header() {
+		let redirected = ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8:1::1".parse().unwrap()).unwrap()
+			.payload(&[0xff; 1500][..]).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v6::Builder::default()
+			.hop_limit(255).unwrap()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::1".parse().unwrap()).unwrap()
+			.icmp().unwrap().redirect().unwrap()
+				.target("fe80::2".parse().unwrap()).unwrap()
+				.destination("2001:db8:1::1".parse().unwrap()).unwrap()
+				.target_link_layer_address("e4:b3:18:26:63:a4".parse().unwrap()).unwrap()
+				.redirected_header(&redirected).unwrap()
+				.build().unwrap();
+
+		assert!(packet.len() <= 1280);
+
+		let ip   = ip::v6::Packet::new(packet).unwrap();
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let redirect = icmp.redirect().unwrap();
+		assert_eq!(redirect.target(), "fe80::2".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(redirect.destination(), "2001:db8:1::1".parse::<Ipv6Addr>().unwrap());
+
+		let options = redirect.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 2);
+		assert_eq!(options[1].redirected_header().unwrap().destination(),
+			"2001:db8:1::1".parse::<Ipv6Addr>().unwrap());
+	}
+}
@@ -0,0 +1,232 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::Kind;
+use crate::icmpv6::ndp::builder as option;
+use crate::icmpv6::ndp::option::Kind as OptionKind;
+use crate::icmpv6::ndp::Preference;
+use crate::icmpv6::ndp::flag::{RouterFlags, PrefixFlags};
+use crate::icmpv6::ndp::router_advertisement::Packet;
+
+/// Router Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(16)?;
+		buffer.data_mut()[0] = Kind::RouterAdvertisement.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Default hop limit for outgoing packets.
+	pub fn hop_limit(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_hop_limit(value)?;
+		Ok(self)
+	}
+
+	/// Advertisement flags.
+	pub fn flags(mut self, value: RouterFlags) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_flags(value)?;
+		Ok(self)
+	}
+
+	/// Preference of the router as a default router.
+	pub fn preference(mut self, value: Preference) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_preference(value)?;
+		Ok(self)
+	}
+
+	/// Seconds the router can be used as default router.
+	pub fn lifetime(mut self, value: u16) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_lifetime(value)?;
+		Ok(self)
+	}
+
+	/// Milliseconds a neighbor is considered reachable.
+	pub fn reachable_time(mut self, value: u32) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_reachable_time(value)?;
+		Ok(self)
+	}
+
+	/// Milliseconds between retransmitted Neighbor Solicitations.
+	pub fn retransmit_timer(mut self, value: u32) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_retransmit_timer(value)?;
+		Ok(self)
+	}
+
+	/// Append a Source Link-Layer Address option.
+	pub fn source_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		option::link_layer_address(&mut self.buffer, OptionKind::SourceLinkLayerAddress, value)?;
+		Ok(self)
+	}
+
+	/// Append an MTU option.
+	pub fn mtu(mut self, value: u32) -> Result<Self> {
+		option::mtu(&mut self.buffer, value)?;
+		Ok(self)
+	}
+
+	/// Append a Prefix Information option.
+	pub fn prefix_information(mut self, prefix: Ipv6Addr, length: u8, flags: PrefixFlags, valid: u32, preferred: u32) -> Result<Self> {
+		option::prefix_information(&mut self.buffer, prefix, length, flags, valid, preferred)?;
+		Ok(self)
+	}
+
+	/// Append a Route Information option.
+	pub fn route_information(mut self, prefix: Ipv6Addr, length: u8, preference: Preference, lifetime: u32) -> Result<Self> {
+		option::route_information(&mut self.buffer, prefix, length, preference, lifetime)?;
+		Ok(self)
+	}
+
+	/// Append a Recursive DNS Server option.
+	pub fn recursive_dns_server(mut self, lifetime: u32, servers: &[Ipv6Addr]) -> Result<Self> {
+		option::recursive_dns_server(&mut self.buffer, lifetime, servers)?;
+		Ok(self)
+	}
+
+	/// Append a DNS Search List option.
+	pub fn dns_search_list(mut self, lifetime: u32, domains: &[&str]) -> Result<Self> {
+		option::dns_search_list(&mut self.buffer, lifetime, domains)?;
+		Ok(self)
+	}
+
+	/// Append a raw option, padded to a multiple of 8 octets.
+	pub fn option(mut self, kind: OptionKind, value: &[u8]) -> Result<Self> {
+		option::option(&mut self.buffer, kind, value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmpv6;
+	use crate::icmpv6::ndp::{flag, option, Preference};
+
+	#[test]
+	fn options() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(255).unwrap()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("ff02::1".parse().unwrap()).unwrap()
+			.icmp().unwrap().router_advertisement().unwrap()
+				.hop_limit(64).unwrap()
+				.flags(flag::RouterFlags::OTHER).unwrap()
+				.preference(Preference::High).unwrap()
+				.lifetime(1800).unwrap()
+				.source_link_layer_address("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+				.mtu(1500).unwrap()
+				.prefix_information("2001:db8:1::".parse().unwrap(), 64,
+					flag::PrefixFlags::ON_LINK | flag::PrefixFlags::AUTONOMOUS, 86400, 14400).unwrap()
+				.route_information("2001:db8:2::".parse().unwrap(), 48, Preference::Low, 3600).unwrap()
+				.recursive_dns_server(600, &["2001:db8::53".parse().unwrap()]).unwrap()
+				.dns_search_list(600, &["example.com", "lan"]).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::new(packet).unwrap();
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmpv6::Kind::RouterAdvertisement);
+		assert!(icmp.is_valid(&ip));
+
+		let ra = icmp.router_advertisement().unwrap();
+		assert_eq!(ra.hop_limit(), 64);
+		assert_eq!(ra.flags(), flag::RouterFlags::OTHER);
+		assert_eq!(ra.preference(), Preference::High);
+		assert_eq!(ra.lifetime(), 1800);
+		assert_eq!(ra.reachable_time(), 0);
+
+		let options = ra.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 6);
+
+		assert_eq!(options[0].kind(), option::Kind::SourceLinkLayerAddress);
+		assert_eq!(options[0].link_layer_address().unwrap(), "e4:b3:18:26:63:a3".parse().unwrap());
+
+		assert_eq!(options[1].mtu().unwrap(), 1500);
+
+		let prefix = options[2].prefix_information().unwrap();
+		assert_eq!(prefix.prefix(), "2001:db8:1::".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(prefix.prefix_length(), 64);
+		assert_eq!(prefix.flags(), flag::PrefixFlags::ON_LINK | flag::PrefixFlags::AUTONOMOUS);
+		assert_eq!(prefix.valid_lifetime(), 86400);
+		assert_eq!(prefix.preferred_lifetime(), 14400);
+
+		let route = options[3].route_information().unwrap();
+		assert_eq!(options[3].length(), 2);
+		assert_eq!(route.prefix(), "2001:db8:2::".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(route.prefix_length(), 48);
+		assert_eq!(route.preference(), Preference::Low);
+		assert_eq!(route.lifetime(), 3600);
+
+		let rdnss = options[4].recursive_dns_server().unwrap();
+		assert_eq!(rdnss.lifetime(), 600);
+		assert_eq!(rdnss.servers().collect::<Vec<_>>(), vec!["2001:db8::53".parse::<Ipv6Addr>().unwrap()]);
+
+		let dnssl = options[5].dns_search_list().unwrap();
+		assert_eq!(dnssl.lifetime(), 600);
+		assert_eq!(dnssl.domains().unwrap(), vec!["example.com", "lan"]);
+		assert!(options[5].mtu().is_err());
+	}
+}
@@ -0,0 +1,307 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmpv6::Kind;
+use crate::icmpv6::checksum;
+
+/// ICMPv6 packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmpv6::Packet")
+			.field("kind", &self.kind())
+			.field("code", &self.code())
+			.field("checksum", &self.checksum())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an ICMPv6 packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an ICMPv6 packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(4)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(4)
+	}
+}
+
+macro_rules! kind {
+	($(#[$attr:meta])* fn $module:ident[$mutable:ident]) => (
+		kind!($(#[$attr])* fn $module[$mutable] in $module);
+	);
+
+	($(#[$attr:meta])* fn $name:ident[$mutable:ident] in $($module:ident)::+) => (
+		$(#[$attr])*
+		pub fn $name(&self) -> Result<crate::icmpv6::$($module)::+::Packet<&B>> {
+			crate::icmpv6::$($module)::+::Packet::new(&self.buffer)
+		}
+
+		$(#[$attr])*
+		pub fn $mutable(&mut self) -> Result<crate::icmpv6::$($module)::+::Packet<&mut B>> {
+			crate::icmpv6::$($module)::+::Packet::new(&mut self.buffer)
+		}
+	)
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Packet type.
+	pub fn kind(&self) -> Kind {
+		Kind::from(self.buffer.as_ref()[0])
+	}
+
+	/// Packet code.
+	pub fn code(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Packet checksum.
+	pub fn checksum(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Verify the packet is valid by calculating the checksum.
+	pub fn is_valid<I: AsRef<[u8]>>(&self, ip: &ip::v6::Packet<I>) -> bool {
+		checksum(ip, self.buffer.as_ref()) == self.checksum()
+	}
+
+	kind!(/// Parse an Echo Request/Reply packet.
+		fn echo[echo_mut]);
+
+	kind!(/// Parse a Destination Unreachable or Time Exceeded packet.
+		fn previous[previous_mut]);
+
+	kind!(/// Parse a Packet Too Big packet.
+		fn packet_too_big[packet_too_big_mut]);
+
+	kind!(/// Parse a Parameter Problem packet.
+		fn parameter_problem[parameter_problem_mut]);
+
+	kind!(/// Parse a Multicast Listener Query packet.
+		fn mld_query[mld_query_mut] in mld::query);
+
+	kind!(/// Parse a Multicast Listener Report/Done packet.
+		fn mld_report[mld_report_mut] in mld::report);
+
+	kind!(/// Parse a Version 2 Multicast Listener Report packet.
+		fn mld_report_v2[mld_report_v2_mut] in mld::report_v2);
+
+	kind!(/// Parse a Router Solicitation packet.
+		fn router_solicitation[router_solicitation_mut] in ndp::router_solicitation);
+
+	kind!(/// Parse a Router Advertisement packet.
+		fn router_advertisement[router_advertisement_mut] in ndp::router_advertisement);
+
+	kind!(/// Parse a Neighbor Solicitation packet.
+		fn neighbor_solicitation[neighbor_solicitation_mut] in ndp::neighbor_solicitation);
+
+	kind!(/// Parse a Neighbor Advertisement packet.
+		fn neighbor_advertisement[neighbor_advertisement_mut] in ndp::neighbor_advertisement);
+
+	kind!(/// Parse a Redirect packet.
+		fn redirect[redirect_mut] in ndp::redirect);
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Packet type.
+	pub fn set_kind(&mut self, value: Kind) -> Result<&mut Self> {
+		self.buffer.as_mut()[0] = value.into();
+
+		Ok(self)
+	}
+
+	/// Packet code.
+	pub fn set_code(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[1] = value;
+
+		Ok(self)
+	}
+
+	/// Set the checksum value.
+	pub fn set_checksum(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Recalculate and set the checksum value.
+	pub fn update_checksum<I: AsRef<[u8]>>(&mut self, ip: &ip::v6::Packet<I>) -> Result<&mut Self> {
+		let checksum = checksum(ip, self.buffer.as_ref());
+		self.set_checksum(checksum)
+	}
+}
+
+/// Checked wrapper for ICMPv6 packets.
+///
+/// # Note
+///
+/// The checksum recalculation happens on `Drop`, so don't leak it.
+pub struct Checked<'a, 'b, P, I>
+	where P: PM + AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      I: AsRef<[u8]> + 'b
+{
+	pub(in crate::icmpv6) packet: &'a mut P,
+	pub(in crate::icmpv6) ip:     &'b ip::v6::Packet<I>,
+}
+
+impl<'a, 'b, P, I> Drop for Checked<'a, 'b, P, I>
+	where P: PM + AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      I: AsRef<[u8]> + 'b
+{
+	fn drop(&mut self) {
+		let checksum = checksum(self.ip, self.packet.as_ref());
+		Cursor::new(&mut self.packet.as_mut()[2 ..])
+			.write_u16::<BigEndian>(checksum).unwrap();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn echo() {
+		let raw = [
+			0x60, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x3a, 0x40,
+			0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+			0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+			0x80, 0x00, 0x9a, 0xaf, 0x00, 0x2a, 0x00, 0x01, 0x74, 0x65, 0x73, 0x74];
+
+		let ip   = ip::v6::Packet::new(&raw[..]).unwrap();
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(icmp.kind(), icmpv6::Kind::EchoRequest);
+		assert!(!icmp.kind().is_error());
+		assert!(icmp.is_valid(&ip));
+
+		let echo = icmp.echo().unwrap();
+		assert!(echo.is_request());
+		assert_eq!(echo.identifier(), 42);
+		assert_eq!(echo.sequence(), 1);
+		assert_eq!(echo.payload(), b"test");
+
+		assert!(icmp.previous().is_err());
+	}
+
+	#[test]
+	fn checked() {
+		let mut raw = [
+			0x60, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x3a, 0x40,
+			0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+			0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+			0x80, 0x00, 0x9a, 0xaf, 0x00, 0x2a, 0x00, 0x01, 0x74, 0x65, 0x73, 0x74];
+
+		let (header, payload) = raw.split_at_mut(40);
+		let ip = ip::v6::Packet::unchecked(&header[..]);
+
+		let mut icmp = icmpv6::Packet::new(payload).unwrap();
+		{
+			let mut echo = icmp.echo_mut().unwrap();
+			echo.checked(&ip).make_reply().unwrap().set_sequence(2).unwrap();
+		}
+
+		assert_eq!(icmp.kind(), icmpv6::Kind::EchoReply);
+		assert_eq!(icmp.echo().unwrap().sequence(), 2);
+		assert!(icmp.is_valid(&ip));
+	}
+}
@@ -0,0 +1,155 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmpv6::builder;
+use crate::icmpv6::{Kind, code};
+use crate::icmpv6::previous::Packet;
+
+/// Destination Unreachable and Time Exceeded packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+	ip:        (usize, usize),
+
+	kind:    bool,
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+			ip:        ip,
+
+			kind:    false,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a Destination Unreachable with the given code.
+	pub fn destination_unreachable(mut self, value: code::DestinationUnreachable) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::DestinationUnreachable.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Make it a Time Exceeded with the given code.
+	pub fn time_exceeded(mut self, value: code::TimeExceeded) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::TimeExceeded.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Packet to cause the message, truncated to fit the minimum IPv6 MTU.
+	pub fn packet<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+		builder::invoking(&mut self.buffer, self.ip, value)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmpv6;
+
+	#[test]
+	fn time_exceeded() {
+		let invoking = ip::v6::Builder::default()
+			.hop_limit(1).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(33434).unwrap()
+				.payload(&[0; 2000][..]).unwrap()
+				.build().unwrap();
+
+		let packet = ip::v6::Builder::default()
+			.source("2001:db8::fe".parse().unwrap()).unwrap()
+			.destination("2001:db8::1".parse().unwrap()).unwrap()
+			.icmp().unwrap().previous().unwrap()
+				.time_exceeded(icmpv6::code::TimeExceeded::HopLimitExceeded).unwrap()
+				.packet(&invoking).unwrap()
+				.build().unwrap();
+
+		assert_eq!(packet.len(), 1280);
+
+		let ip   = ip::v6::Packet::new(packet).unwrap();
+		let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid(&ip));
+		assert_eq!(icmp.kind(), icmpv6::Kind::TimeExceeded);
+		assert_eq!(icmpv6::code::TimeExceeded::from(icmp.code()),
+			icmpv6::code::TimeExceeded::HopLimitExceeded);
+
+		let previous = icmp.previous().unwrap();
+		assert!(previous.is_time_exceeded());
+		assert_eq!(previous.packet().unwrap().destination(),
+			"2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap());
+	}
+}
@@ -0,0 +1,91 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::igmp::checksum;
+use crate::igmp::{query, report, report_v3};
+
+/// IGMP packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(buffer: B) -> Result<Self> {
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(self) -> Result<B::Inner> {
+		Err(Error::InvalidPacket)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Build a Membership Query packet.
+	pub fn query(self) -> Result<query::Builder<B>> {
+		let mut query = query::Builder::with(self.buffer)?;
+		query.finalizer().extend(self.finalizer);
+
+		Ok(query)
+	}
+
+	/// Build a Version 1 or 2 Membership Report or a Leave Group packet.
+	pub fn report(self) -> Result<report::Builder<B>> {
+		let mut report = report::Builder::with(self.buffer)?;
+		report.finalizer().extend(self.finalizer);
+
+		Ok(report)
+	}
+
+	/// Build a Version 3 Membership Report packet.
+	pub fn report_v3(self) -> Result<report_v3::Builder<B>> {
+		let mut report = report_v3::Builder::with(self.buffer)?;
+		report.finalizer().extend(self.finalizer);
+
+		Ok(report)
+	}
+}
+
+pub(in crate::igmp) fn prepare<B: Buffer>(finalizer: &mut Finalization, buffer: &B) {
+	let offset = buffer.offset();
+	let length = buffer.length();
+
+	finalizer.add(move |out| {
+		let checksum = checksum(&out[offset .. offset + length]);
+		Cursor::new(&mut out[offset + 2 ..])
+			.write_u16::<BigEndian>(checksum)?;
+
+		Ok(())
+	});
+}
@@ -0,0 +1,65 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// IGMP packet types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+	///
+	MembershipQuery,
+
+	///
+	MembershipReportV1,
+
+	///
+	MembershipReportV2,
+
+	///
+	LeaveGroup,
+
+	///
+	MembershipReportV3,
+
+	///
+	Unknown(u8),
+}
+
+impl From<u8> for Kind {
+	fn from(value: u8) -> Kind {
+		use self::Kind::*;
+
+		match value {
+			0x11 => MembershipQuery,
+			0x12 => MembershipReportV1,
+			0x16 => MembershipReportV2,
+			0x17 => LeaveGroup,
+			0x22 => MembershipReportV3,
+			n    => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Kind {
+	fn into(self) -> u8 {
+		use self::Kind::*;
+
+		match self {
+			MembershipQuery    => 0x11,
+			MembershipReportV1 => 0x12,
+			MembershipReportV2 => 0x16,
+			LeaveGroup         => 0x17,
+			MembershipReportV3 => 0x22,
+			Unknown(n)         => n,
+		}
+	}
+}
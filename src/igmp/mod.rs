@@ -0,0 +1,92 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+mod kind;
+pub use self::kind::Kind;
+
+mod packet;
+pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
+
+/// Membership Query, for all IGMP versions.
+pub mod query;
+
+/// Version 1 and 2 Membership Report and Leave Group.
+pub mod report;
+
+/// Version 3 Membership Report.
+pub mod report_v3;
+
+/// Group records.
+pub mod record;
+pub use self::record::{Record, RecordIter};
+
+/// Calculate the checksum for an IGMP packet.
+pub fn checksum(buffer: &[u8]) -> u16 {
+	use std::io::Cursor;
+	use byteorder::{ReadBytesExt, BigEndian};
+
+	let mut result = 0xffffu32;
+	let mut buffer = Cursor::new(buffer);
+
+	while let Ok(value) = buffer.read_u16::<BigEndian>() {
+		// Skip checksum field.
+		if buffer.position() == 4 {
+			continue;
+		}
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
+	if buffer.get_ref().len() % 2 == 1 {
+		// if we have a trailing byte, make a padded 16-bit value.
+		let value = u16::from(buffer.get_ref()[buffer.get_ref().len() - 1]) << 8;
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
+	!result as u16
+}
+
+/// Decode an IGMPv3 Max Resp Code into tenths of a second.
+pub fn maximum_response_time(code: u8) -> u32 {
+	exponential(code)
+}
+
+/// Decode an IGMPv3 Querier's Query Interval Code into seconds.
+pub fn query_interval(code: u8) -> u32 {
+	exponential(code)
+}
+
+fn exponential(code: u8) -> u32 {
+	if code < 0x80 {
+		u32::from(code)
+	}
+	else {
+		let exponent = (code >> 4) & 0x07;
+		let mantissa = code & 0x0f;
+
+		u32::from(mantissa | 0x10) << (exponent + 3)
+	}
+}
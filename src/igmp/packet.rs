@@ -0,0 +1,213 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::igmp::Kind;
+use crate::igmp::checksum;
+
+/// IGMP packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  4,
+		size: p => p.buffer.as_ref().len() - 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct(if self.is_valid() { "igmp::Packet" } else { "igmp::Packet!" })
+			.field("kind", &self.kind())
+			.field("maximum_response_code", &self.maximum_response_code())
+			.field("checksum", &self.checksum())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an IGMP packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an IGMP packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(4)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(4)
+	}
+}
+
+macro_rules! kind {
+	($(#[$attr:meta])* fn $module:ident[$mutable:ident]) => (
+		$(#[$attr])*
+		pub fn $module(&self) -> Result<crate::igmp::$module::Packet<&B>> {
+			crate::igmp::$module::Packet::new(&self.buffer)
+		}
+
+		$(#[$attr])*
+		pub fn $mutable(&mut self) -> Result<crate::igmp::$module::Packet<&mut B>> {
+			crate::igmp::$module::Packet::new(&mut self.buffer)
+		}
+	)
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Packet type.
+	pub fn kind(&self) -> Kind {
+		Kind::from(self.buffer.as_ref()[0])
+	}
+
+	/// Max Resp Code, it's unused in anything but queries.
+	pub fn maximum_response_code(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Packet checksum.
+	pub fn checksum(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Verify the packet is valid by calculating the checksum.
+	pub fn is_valid(&self) -> bool {
+		checksum(self.buffer.as_ref()) == self.checksum()
+	}
+
+	kind!(/// Parse a Membership Query packet.
+		fn query[query_mut]);
+
+	kind!(/// Parse a Version 1 or 2 Membership Report or a Leave Group packet.
+		fn report[report_mut]);
+
+	kind!(/// Parse a Version 3 Membership Report packet.
+		fn report_v3[report_v3_mut]);
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Set the checksum value.
+	pub fn set_checksum(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Recalculate and set the checksum value.
+	pub fn update_checksum(&mut self) -> Result<&mut Self> {
+		let checksum = checksum(self.buffer.as_ref());
+		self.set_checksum(checksum)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::igmp;
+
+	#[test]
+	fn report() {
+		let raw = [
+			0x46, 0x00, 0x00, 0x20, 0x00, 0x00, 0x40, 0x00, 0x01, 0x02, 0x42, 0xe5,
+			0xc0, 0xa8, 0x00, 0x4f, 0xe0, 0x00, 0x00, 0xfb, 0x94, 0x04, 0x00, 0x00,
+			0x16, 0x00, 0x09, 0x04, 0xe0, 0x00, 0x00, 0xfb];
+
+		let ip = ip::v4::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ip.protocol(), ip::Protocol::Igmp);
+
+		let igmp = igmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(igmp.kind(), igmp::Kind::MembershipReportV2);
+		assert!(igmp.is_valid());
+
+		let report = igmp.report().unwrap();
+		assert!(!report.is_leave());
+		assert_eq!(report.group(), "224.0.0.251".parse::<Ipv4Addr>().unwrap());
+
+		assert!(igmp.query().is_err());
+	}
+}
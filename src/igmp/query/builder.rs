@@ -0,0 +1,206 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::igmp::builder;
+use crate::igmp::Kind;
+use crate::igmp::query::Packet;
+
+/// Membership Query packet builder.
+///
+/// The query is an IGMPv2 one, or IGMPv1 if the Max Resp Code is left to
+/// zero, unless any of the IGMPv3 fields is set.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	sources: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::MembershipQuery.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+
+			sources: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Max Resp Code.
+	pub fn maximum_response_code(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_maximum_response_code(value)?;
+		Ok(self)
+	}
+
+	/// Group address being queried, leave it unset for a General Query.
+	pub fn group(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_group(value)?;
+		Ok(self)
+	}
+
+	/// Make it an IGMPv3 query.
+	pub fn v3(mut self) -> Result<Self> {
+		if self.buffer.length() == 8 {
+			self.buffer.more(4)?;
+		}
+
+		Ok(self)
+	}
+
+	/// Suppress Router-Side Processing flag, makes it an IGMPv3 query.
+	pub fn suppress(mut self, value: bool) -> Result<Self> {
+		self = self.v3()?;
+		Packet::unchecked(self.buffer.data_mut()).set_suppress(value)?;
+		Ok(self)
+	}
+
+	/// Querier's Robustness Variable, makes it an IGMPv3 query.
+	pub fn robustness(mut self, value: u8) -> Result<Self> {
+		self = self.v3()?;
+		Packet::unchecked(self.buffer.data_mut()).set_robustness(value)?;
+		Ok(self)
+	}
+
+	/// Querier's Query Interval Code, makes it an IGMPv3 query.
+	pub fn query_interval_code(mut self, value: u8) -> Result<Self> {
+		self = self.v3()?;
+		Packet::unchecked(self.buffer.data_mut()).set_query_interval_code(value)?;
+		Ok(self)
+	}
+
+	/// Source addresses for a Group-and-Source-Specific Query, makes it an
+	/// IGMPv3 query.
+	pub fn sources<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(mut self, value: T) -> Result<Self> {
+		if self.sources {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self = self.v3()?;
+		self.sources = true;
+
+		let mut count = 0usize;
+
+		for source in value {
+			let offset = self.buffer.length();
+			self.buffer.more(4)?;
+			self.buffer.data_mut()[offset ..].copy_from_slice(&source.octets());
+
+			count += 1;
+		}
+
+		if count > u16::max_value() as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		Packet::unchecked(self.buffer.data_mut()).set_number_of_sources(count as u16)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::igmp;
+
+	#[test]
+	fn v2() {
+		let packet = ip::v4::Builder::default()
+			.source("192.168.0.1".parse().unwrap()).unwrap()
+			.destination("224.0.0.1".parse().unwrap()).unwrap()
+			.igmp().unwrap().query().unwrap()
+				.maximum_response_code(100).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v4::Packet::new(packet).unwrap();
+		assert!(ip.is_valid());
+
+		let igmp = igmp::Packet::new(ip.payload()).unwrap();
+		assert!(igmp.is_valid());
+
+		let query = igmp.query().unwrap();
+		assert_eq!(query.version(), 2);
+		assert!(query.is_general());
+		assert_eq!(query.maximum_response_time(), 100);
+	}
+
+	#[test]
+	fn v3() {
+		let packet = igmp::Builder::default()
+			.query().unwrap()
+				.maximum_response_code(0x8a).unwrap()
+				.group("232.1.1.1".parse().unwrap()).unwrap()
+				.robustness(2).unwrap()
+				.query_interval_code(125).unwrap()
+				.sources(&["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()]).unwrap()
+				.build().unwrap();
+
+		let igmp = igmp::Packet::new(packet).unwrap();
+		assert!(igmp.is_valid());
+
+		let query = igmp.query().unwrap();
+		assert_eq!(query.version(), 3);
+		assert_eq!(query.group(), "232.1.1.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(query.maximum_response_time(), 0x1a << 3);
+		assert!(!query.suppress());
+		assert_eq!(query.robustness(), 2);
+		assert_eq!(query.query_interval(), 125);
+		assert_eq!(query.sources().collect::<Vec<_>>(), vec![
+			"10.0.0.1".parse::<Ipv4Addr>().unwrap(),
+			"10.0.0.2".parse::<Ipv4Addr>().unwrap()]);
+	}
+}
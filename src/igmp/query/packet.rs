@@ -0,0 +1,317 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::igmp;
+use crate::igmp::Kind;
+
+/// Membership Query packet parser.
+///
+/// # Note
+///
+/// The version of a query is told apart by its length and Max Resp Code, so
+/// the buffer must not contain anything past the IGMP message.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  12,
+		size: p => if p.version() == 3 { 12 } else { 8 },
+	}
+
+	payload {
+		min:  0,
+		size: p => p.number_of_sources() as usize * 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("igmp::query::Packet")
+			.field("version", &self.version())
+			.field("maximum_response_code", &self.maximum_response_code())
+			.field("group", &self.group())
+			.field("sources", &self.sources().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Membership Query packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Membership Query packet, checking the buffer contents are
+	/// correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if Kind::from(packet.buffer.as_ref()[0]) != Kind::MembershipQuery {
+			Err(Error::InvalidPacket)?
+		}
+
+		// Queries between 9 and 11 octets must be ignored.
+		if packet.buffer.as_ref().len() > 8 && packet.buffer.as_ref().len() < 12 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < packet.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		let header = if self.version() == 3 { 12 } else { 8 };
+		let size   = self.size();
+
+		self.buffer.as_ref()[.. size].split_at(header)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let header = if self.version() == 3 { 12 } else { 8 };
+		let size   = self.size();
+
+		self.buffer.as_mut()[.. size].split_at_mut(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// IGMP version of the query.
+	pub fn version(&self) -> u8 {
+		if self.buffer.as_ref().len() >= 12 {
+			3
+		}
+		else if self.maximum_response_code() == 0 {
+			1
+		}
+		else {
+			2
+		}
+	}
+
+	/// Max Resp Code.
+	pub fn maximum_response_code(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Maximum response time in tenths of a second, IGMPv1 queries don't
+	/// carry it and are answered within 10 seconds.
+	pub fn maximum_response_time(&self) -> u32 {
+		match self.version() {
+			1 => 100,
+			2 => u32::from(self.maximum_response_code()),
+			_ => igmp::maximum_response_time(self.maximum_response_code()),
+		}
+	}
+
+	/// Group address being queried, unspecified for a General Query.
+	pub fn group(&self) -> Ipv4Addr {
+		Ipv4Addr::new(
+			self.buffer.as_ref()[4],
+			self.buffer.as_ref()[5],
+			self.buffer.as_ref()[6],
+			self.buffer.as_ref()[7])
+	}
+
+	/// Whether the query is a General Query.
+	pub fn is_general(&self) -> bool {
+		self.group().is_unspecified()
+	}
+
+	/// Whether routers must suppress timer updates on receipt (IGMPv3 only).
+	pub fn suppress(&self) -> bool {
+		self.version() == 3 && self.buffer.as_ref()[8] & 0x08 != 0
+	}
+
+	/// Querier's Robustness Variable (IGMPv3 only).
+	pub fn robustness(&self) -> u8 {
+		if self.version() == 3 {
+			self.buffer.as_ref()[8] & 0x07
+		}
+		else {
+			0
+		}
+	}
+
+	/// Querier's Query Interval Code (IGMPv3 only).
+	pub fn query_interval_code(&self) -> u8 {
+		if self.version() == 3 {
+			self.buffer.as_ref()[9]
+		}
+		else {
+			0
+		}
+	}
+
+	/// Querier's query interval in seconds (IGMPv3 only).
+	pub fn query_interval(&self) -> u32 {
+		igmp::query_interval(self.query_interval_code())
+	}
+
+	/// Number of sources (IGMPv3 only).
+	pub fn number_of_sources(&self) -> u16 {
+		if self.version() == 3 {
+			(&self.buffer.as_ref()[10 ..]).read_u16::<BigEndian>().unwrap()
+		}
+		else {
+			0
+		}
+	}
+
+	/// Source addresses of a Group-and-Source-Specific Query.
+	pub fn sources(&self) -> impl Iterator<Item = Ipv4Addr> + '_ {
+		self.payload().chunks(4).map(|chunk|
+			Ipv4Addr::new(chunk[0], chunk[1], chunk[2], chunk[3]))
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Max Resp Code.
+	pub fn set_maximum_response_code(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[1] = value;
+
+		Ok(self)
+	}
+
+	/// Group address being queried.
+	pub fn set_group(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[4 .. 8].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Suppress Router-Side Processing flag (IGMPv3 only).
+	pub fn set_suppress(&mut self, value: bool) -> Result<&mut Self> {
+		if self.version() != 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let buffer = self.buffer.as_mut();
+
+		if value {
+			buffer[8] |= 0x08;
+		}
+		else {
+			buffer[8] &= !0x08;
+		}
+
+		Ok(self)
+	}
+
+	/// Querier's Robustness Variable (IGMPv3 only).
+	pub fn set_robustness(&mut self, value: u8) -> Result<&mut Self> {
+		if self.version() != 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if value > 7 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[8] = (buffer[8] & !0x07) | value;
+
+		Ok(self)
+	}
+
+	/// Querier's Query Interval Code (IGMPv3 only).
+	pub fn set_query_interval_code(&mut self, value: u8) -> Result<&mut Self> {
+		if self.version() != 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.buffer.as_mut()[9] = value;
+
+		Ok(self)
+	}
+
+	/// Number of sources (IGMPv3 only).
+	pub fn set_number_of_sources(&mut self, value: u16) -> Result<&mut Self> {
+		if self.version() != 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Cursor::new(&mut self.buffer.as_mut()[10 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
@@ -0,0 +1,144 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::igmp::builder;
+use crate::igmp::Kind;
+use crate::igmp::report::Packet;
+
+/// Version 1 or 2 Membership Report and Leave Group packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	kind: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+
+			kind: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a Version 1 Membership Report.
+	pub fn v1(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::MembershipReportV1.into();
+
+		Ok(self)
+	}
+
+	/// Make it a Version 2 Membership Report.
+	pub fn v2(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::MembershipReportV2.into();
+
+		Ok(self)
+	}
+
+	/// Make it a Leave Group.
+	pub fn leave(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::LeaveGroup.into();
+
+		Ok(self)
+	}
+
+	/// Group address being reported or left.
+	pub fn group(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_group(value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::igmp;
+
+	#[test]
+	fn leave() {
+		let packet = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("224.0.0.2".parse().unwrap()).unwrap()
+			.igmp().unwrap().report().unwrap()
+				.leave().unwrap()
+				.group("239.1.2.3".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v4::Packet::new(packet).unwrap();
+		assert!(ip.is_valid());
+		assert_eq!(ip.header(), 6);
+		assert_eq!(ip.ttl(), 1);
+		assert_eq!(ip.length(), 32);
+		assert_eq!(&ip.as_ref()[20 .. 24], &[0x94, 0x04, 0x00, 0x00]);
+
+		let igmp = igmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(igmp.kind(), igmp::Kind::LeaveGroup);
+		assert!(igmp.is_valid());
+
+		let report = igmp.report().unwrap();
+		assert!(report.is_leave());
+		assert_eq!(report.group(), "239.1.2.3".parse::<Ipv4Addr>().unwrap());
+	}
+}
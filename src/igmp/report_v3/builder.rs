@@ -0,0 +1,154 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::igmp::builder;
+use crate::igmp::Kind;
+use crate::igmp::record;
+use crate::igmp::report_v3::Packet;
+
+/// Version 3 Membership Report packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	records: u16,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::MembershipReportV3.into();
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+
+			records: 0,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Append a group record.
+	pub fn record<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(mut self, kind: record::Kind, group: Ipv4Addr, sources: T) -> Result<Self> {
+		if self.records == u16::max_value() {
+			Err(Error::InvalidPacket)?
+		}
+
+		let start = self.buffer.length();
+		self.buffer.more(8)?;
+
+		let mut count = 0usize;
+
+		for source in sources {
+			let offset = self.buffer.length();
+			self.buffer.more(4)?;
+			self.buffer.data_mut()[offset ..].copy_from_slice(&source.octets());
+
+			count += 1;
+		}
+
+		if count > u16::max_value() as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		{
+			let data = &mut self.buffer.data_mut()[start ..];
+			data[0] = kind.into();
+			data[2 .. 4].copy_from_slice(&(count as u16).to_be_bytes());
+			data[4 .. 8].copy_from_slice(&group.octets());
+		}
+
+		self.records += 1;
+		Packet::unchecked(self.buffer.data_mut()).set_number_of_records(self.records)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::igmp;
+
+	#[test]
+	fn records() {
+		let sources = ["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+
+		let packet = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("224.0.0.22".parse().unwrap()).unwrap()
+			.igmp().unwrap().report_v3().unwrap()
+				.record(igmp::record::Kind::ModeIsInclude, "232.1.1.1".parse().unwrap(), &sources).unwrap()
+				.record(igmp::record::Kind::ChangeToExclude, "239.255.255.250".parse().unwrap(), &[]).unwrap()
+				.build().unwrap();
+
+		let ip = ip::v4::Packet::new(packet).unwrap();
+		assert!(ip.is_valid());
+
+		let igmp = igmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(igmp.kind(), igmp::Kind::MembershipReportV3);
+		assert!(igmp.is_valid());
+
+		let report = igmp.report_v3().unwrap();
+		assert_eq!(report.number_of_records(), 2);
+
+		let records = report.records().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(records[0].kind(), igmp::record::Kind::ModeIsInclude);
+		assert_eq!(records[0].group(), "232.1.1.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(records[0].sources().collect::<Vec<_>>(), sources);
+
+		assert_eq!(records[1].kind(), igmp::record::Kind::ChangeToExclude);
+		assert_eq!(records[1].sources().count(), 0);
+		assert_eq!(records[1].group(), "239.255.255.250".parse::<Ipv4Addr>().unwrap());
+	}
+}
@@ -21,6 +21,8 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+mod reassembly;
+
 /// IPv4 packet parser and builder.
 pub mod v4;
 
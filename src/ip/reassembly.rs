@@ -0,0 +1,110 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::collections::BTreeMap;
+
+use crate::error::*;
+
+/// Fragment data received so far for a datagram.
+#[derive(Default, Debug)]
+pub(in crate::ip) struct Fragments {
+	fragments: BTreeMap<usize, Vec<u8>>,
+	length:    Option<usize>,
+}
+
+impl Fragments {
+	/// Check a fragment against the ones already received, returning whether
+	/// it carries anything new.
+	///
+	/// Exact duplicates are fine, any other overlap is an error as per RFC
+	/// 5722.
+	pub fn accepts(&self, offset: usize, payload: &[u8], more: bool) -> Result<bool> {
+		let end = offset + payload.len();
+
+		if let Some(length) = self.length {
+			if end > length || (!more && end != length) {
+				Err(Error::InvalidPacket)?
+			}
+		}
+
+		if !more {
+			if let Some((&start, data)) = self.fragments.iter().next_back() {
+				if start + data.len() > end {
+					Err(Error::InvalidPacket)?
+				}
+			}
+		}
+
+		if let Some(data) = self.fragments.get(&offset) {
+			if &data[..] == payload {
+				return Ok(false);
+			}
+		}
+
+		// Fragments never overlap each other, so the last one starting before
+		// the end is the only one that can overlap.
+		if let Some((&start, data)) = self.fragments.range(.. end.max(offset + 1)).next_back() {
+			if start + data.len() > offset || start == offset {
+				Err(Error::InvalidPacket)?
+			}
+		}
+
+		Ok(true)
+	}
+
+	/// Add a fragment, it must have been accepted first.
+	pub fn insert(&mut self, offset: usize, payload: &[u8], more: bool) {
+		self.fragments.insert(offset, payload.to_vec());
+
+		if !more {
+			self.length = Some(offset + payload.len());
+		}
+	}
+
+	/// Check if all the fragments have been received.
+	pub fn is_complete(&self) -> bool {
+		let length = match self.length {
+			Some(length) => length,
+			None         => return false,
+		};
+
+		let mut position = 0;
+
+		for (&start, data) in &self.fragments {
+			if start != position {
+				return false;
+			}
+
+			position += data.len();
+		}
+
+		position == length
+	}
+
+	/// Data of the fragment at the given offset.
+	pub fn get(&self, offset: usize) -> Option<&[u8]> {
+		self.fragments.get(&offset).map(|data| &data[..])
+	}
+
+	/// Length of the reassembled data, if the last fragment was received.
+	pub fn length(&self) -> Option<usize> {
+		self.length
+	}
+
+	/// Append the reassembled data to the buffer.
+	pub fn assemble(&self, buffer: &mut Vec<u8>) {
+		for data in self.fragments.values() {
+			buffer.extend_from_slice(data);
+		}
+	}
+}
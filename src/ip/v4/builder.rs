@@ -23,6 +23,7 @@ use crate::packet::{AsPacket, AsPacketMut};
 use crate::ip::Protocol;
 use crate::ip::v4::Packet;
 use crate::ip::v4::Flags;
+use crate::ip::v4::option;
 use crate::ip::v4::checksum;
 
 /// IPv4 packet builder.
@@ -62,11 +63,9 @@ impl<B: Buffer> Build<B> for Builder<B> {
 	}
 
 	fn build(mut self) -> Result<B::Inner> {
-		self.prepare();
+		self.prepare()?;
 
-		let mut buffer = self.buffer.into_inner();
-		self.finalizer.finalize(buffer.as_mut())?;
-		Ok(buffer)
+		self.finalizer.build(self.buffer)
 	}
 }
 
@@ -97,7 +96,7 @@ macro_rules! protocol {
 			}
 
 			self = self.protocol(Protocol::$protocol)?;
-			self.prepare();
+			self.prepare()?;
 
 			let mut builder = crate::$module::Builder::with(self.buffer)?;
 			builder.finalizer().extend(self.finalizer);
@@ -162,12 +161,105 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
+	/// Add an option, the header is padded to a multiple of 4 octets once
+	/// the options are done.
+	pub fn option(mut self, copied: bool, class: option::Class, number: option::Number, value: &[u8]) -> Result<Self> {
+		if number == option::Number::End || number == option::Number::NoOperation {
+			Err(Error::InvalidValue)?
+		}
+
+		let class:  u8 = class.into();
+		let number: u8 = number.into();
+
+		let mut data = Vec::with_capacity(2 + value.len());
+		data.push(((copied as u8) << 7) | (class << 5) | number);
+		data.push((2 + value.len()) as u8);
+		data.extend_from_slice(value);
+
+		self.extend(&data)?;
+		Ok(self)
+	}
+
+	/// Add a No Operation option, used to align the following option.
+	pub fn nop(mut self) -> Result<Self> {
+		self.extend(&[option::Number::NoOperation.into()])?;
+		Ok(self)
+	}
+
+	/// Add an End of Options List option.
+	pub fn end(mut self) -> Result<Self> {
+		self.extend(&[option::Number::End.into()])?;
+		Ok(self)
+	}
+
+	/// Add a Router Alert option.
+	pub fn router_alert(self, value: u16) -> Result<Self> {
+		self.option(true, option::Class::Control, option::Number::RouterAlert, &value.to_be_bytes())
+	}
+
+	/// Add a Record Route option with room for the given number of addresses.
+	pub fn record_route(self, slots: usize) -> Result<Self> {
+		let mut data = vec![0u8; 1 + slots * 4];
+		data[0] = 4;
+
+		self.option(false, option::Class::Control, option::Number::RecordRoute, &data)
+	}
+
+	/// Add a Loose Source and Record Route option, the route is given in the
+	/// order it's visited and ends with the final destination.
+	///
+	/// # Note
+	///
+	/// The destination address is set to the first hop.
+	pub fn loose_source_route<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, route: T) -> Result<Self> {
+		self.source_route(option::Number::LooseSourceRoute, route)
+	}
+
+	/// Add a Strict Source and Record Route option, the route is given in the
+	/// order it's visited and ends with the final destination.
+	///
+	/// # Note
+	///
+	/// The destination address is set to the first hop.
+	pub fn strict_source_route<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, route: T) -> Result<Self> {
+		self.source_route(option::Number::StrictSourceRoute, route)
+	}
+
+	/// Add a Timestamp option with room for the given number of entries.
+	pub fn timestamp(self, flag: option::TimestampFlag, slots: usize) -> Result<Self> {
+		let size = match flag {
+			option::TimestampFlag::TimestampOnly => 4,
+			option::TimestampFlag::WithAddress   => 8,
+			_                                    => Err(Error::InvalidValue)?,
+		};
+
+		let mut data = vec![0u8; 2 + slots * size];
+		data[0] = 5;
+		data[1] = flag.into();
+
+		self.option(false, option::Class::Debugging, option::Number::TimeStamp, &data)
+	}
+
+	/// Add a Timestamp option where only the given routers record a
+	/// timestamp.
+	pub fn timestamp_prespecified<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, addresses: T) -> Result<Self> {
+		let mut data = vec![5, option::TimestampFlag::Prespecified.into()];
+
+		for address in addresses {
+			data.extend_from_slice(&address.octets());
+			data.extend_from_slice(&[0; 4]);
+		}
+
+		self.option(false, option::Class::Debugging, option::Number::TimeStamp, &data)
+	}
+
 	/// Payload for the packet.
 	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
 		if self.payload {
 			Err(Error::AlreadyDefined)?
 		}
 
+		self.close()?;
 		self.payload = true;
 
 		for byte in value {
@@ -178,27 +270,81 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
-	fn prepare(&mut self) {
+	fn source_route<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(mut self, number: option::Number, route: T) -> Result<Self> {
+		let route = route.into_iter().collect::<Vec<_>>();
+
+		if route.len() < 2 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut data = vec![4];
+		for address in &route[1 ..] {
+			data.extend_from_slice(&address.octets());
+		}
+
+		Packet::unchecked(self.buffer.data_mut()).set_destination(*route[0])?;
+		self.option(true, option::Class::Control, number, &data)
+	}
+
+	/// Append raw option data to the header.
+	fn extend(&mut self, value: &[u8]) -> Result<()> {
+		use crate::size::header::Max;
+
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		if self.buffer.length() + value.len() > Packet::<()>::max() {
+			Err(Error::InvalidPacket)?
+		}
+
+		let offset = self.buffer.length();
+		self.buffer.more(value.len())?;
+		self.buffer.data_mut()[offset ..].copy_from_slice(value);
+		self.options = true;
+
+		Ok(())
+	}
+
+	/// Pad the options to a multiple of 4 octets and write the header length.
+	fn close(&mut self) -> Result<()> {
+		if self.payload {
+			return Ok(());
+		}
+
+		if self.options {
+			let padding = (4 - self.buffer.length() % 4) % 4;
+			self.extend(&[option::Number::End.into(); 3][.. padding])?;
+		}
+
+		let header = self.buffer.length() / 4;
+		self.buffer.data_mut()[0] = (4 << 4) | header as u8;
+
+		Ok(())
+	}
+
+	fn prepare(&mut self) -> Result<()> {
+		self.close()?;
+
 		let offset = self.buffer.offset();
-		let length = self.buffer.length();
 
 		self.finalizer.add(move |out| {
-			// Set the version to 4 and the header length.
-			let header = length / 4;
-			out[offset] = (4 << 4) | header as u8;
+			let header = (out[offset] & 0b1111) as usize * 4;
 
 			// Calculate and write the total length of the packet.
-			let length = length + (out.len() - (offset + length));
+			let length = out.len() - offset;
 			Cursor::new(&mut out[offset + 2 ..])
 				.write_u16::<BigEndian>(length as u16)?;
 
 			// Calculate and write the checksum.
-			let checksum = checksum(&out[offset .. offset + header * 4]);
+			let checksum = checksum(&out[offset .. offset + header]);
 			Cursor::new(&mut out[offset + 10 ..])
 				.write_u16::<BigEndian>(checksum)?;
 
 			Ok(())
 		});
+
+		Ok(())
 	}
 
 	protocol!(/// Build an ICMP packet.
@@ -209,12 +355,31 @@ impl<B: Buffer> Builder<B> {
 
 	protocol!(/// Build a UDP packet.
 		fn udp(Udp));
+
+	/// Build an IGMP packet, adding the Router Alert option and setting the
+	/// TTL to 1.
+	pub fn igmp(mut self) -> Result<crate::igmp::Builder<B>> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		// Router Alert with a value of 0, every router examines the packet.
+		self = self.router_alert(0)?;
+		self = self.ttl(1)?.protocol(Protocol::Igmp)?;
+		self.prepare()?;
+
+		let mut builder = crate::igmp::Builder::with(self.buffer)?;
+		builder.finalizer().extend(self.finalizer);
+
+		Ok(builder)
+	}
 }
 
 #[cfg(test)]
 mod test {
 	use std::net::Ipv4Addr;
 	use crate::builder::Builder;
+	use crate::packet::Packet;
 	use crate::ip;
 	use crate::tcp;
 
@@ -267,4 +432,59 @@ mod test {
 		assert_eq!(packet.destination(), "192.168.0.79".parse::<Ipv4Addr>().unwrap());
 		assert!(packet.is_valid());
 	}
+
+	#[test]
+	fn options() {
+		let packet = ip::v4::Builder::default()
+			.ttl(64).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("10.0.0.1".parse().unwrap()).unwrap()
+			.record_route(2).unwrap()
+			.router_alert(0).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&[0xde, 0xad]).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v4::Packet::new(packet).unwrap();
+		assert_eq!(packet.header(), 9);
+		assert_eq!(packet.length(), 38);
+		assert!(packet.is_valid());
+		assert_eq!(packet.payload(), &[0xde, 0xad]);
+
+		let options = packet.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 2);
+		assert_eq!(options[0].number(), ip::v4::option::Number::RecordRoute);
+		assert_eq!(options[0].length(), 11);
+		assert_eq!(options[1].number(), ip::v4::option::Number::RouterAlert);
+		assert!(options[1].is_copied());
+		assert_eq!(&packet.as_ref()[31 .. 36], &[0x94, 0x04, 0x00, 0x00, 0x00]);
+	}
+
+	#[test]
+	fn source_route() {
+		let packet = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.nop().unwrap()
+			.loose_source_route(&["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()]).unwrap()
+			.timestamp(ip::v4::option::TimestampFlag::TimestampOnly, 1).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v4::Packet::new(packet).unwrap();
+		assert_eq!(packet.header(), 9);
+		assert!(packet.is_valid());
+		assert_eq!(packet.destination(), "10.0.0.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(&packet.as_ref()[20 .. 28], &[0x01, 0x83, 0x07, 0x04, 10, 0, 0, 2]);
+		assert_eq!(&packet.as_ref()[28 .. 36], &[0x44, 0x08, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	}
+
+	#[test]
+	fn options_too_long() {
+		assert!(ip::v4::Builder::default()
+			.record_route(9).unwrap()
+			.router_alert(0).is_err());
+
+		assert!(ip::v4::Builder::default()
+			.payload(&[0x00]).unwrap()
+			.nop().is_err());
+	}
 }
@@ -0,0 +1,249 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::v4::Packet;
+use crate::ip::v4::Flags;
+use crate::ip::v4::option;
+
+/// Split a packet in fragments that fit in the given MTU.
+///
+/// A packet that already fits is returned as a single fragment, while a
+/// packet that doesn't fit but has `DONT_FRAGMENT` set is an error.
+///
+/// # Note
+///
+/// Only the first fragment carries all the options, the following ones only
+/// carry the options with the copied bit set.
+pub fn fragment<B: AsRef<[u8]>>(packet: &Packet<B>, mtu: usize) -> Result<Fragments<'_>> {
+	if packet.header() < 5 {
+		Err(Error::InvalidPacket)?
+	}
+
+	let (header, payload) = packet.split();
+
+	if header.len() + payload.len() > mtu && packet.flags().contains(Flags::DONT_FRAGMENT) {
+		Err(Error::InvalidPacket)?
+	}
+
+	if header.len() + payload.len() > mtu && header.len() + 8 > mtu {
+		Err(Error::InvalidValue)?
+	}
+
+	if packet.offset() as usize + payload.len() / 8 > 0x1fff {
+		Err(Error::InvalidPacket)?
+	}
+
+	// Only the options with the copied bit set are repeated after the first
+	// fragment.
+	let mut rest = header[.. 20].to_vec();
+
+	for option in packet.options() {
+		// Malformed options, like a length below 2 or past the header, would
+		// make the fragments unparsable.
+		let option = option.map_err(|_| Error::InvalidPacket)?;
+
+		if option.is_copied() {
+			rest.extend_from_slice(option.as_ref());
+		}
+	}
+
+	while rest.len() % 4 != 0 {
+		rest.push(option::Number::End.into());
+	}
+
+	rest[0] = (4 << 4) | (rest.len() / 4) as u8;
+
+	Ok(Fragments {
+		first:   header,
+		rest:    rest,
+		payload: payload,
+
+		mtu:      mtu,
+		position: 0,
+		offset:   packet.offset(),
+		more:     packet.flags().contains(Flags::MORE_FRAGMENTS),
+	})
+}
+
+/// Iterator over the fragments of a packet.
+pub struct Fragments<'a> {
+	first:   &'a [u8],
+	rest:    Vec<u8>,
+	payload: &'a [u8],
+
+	mtu:      usize,
+	position: usize,
+	offset:   u16,
+	more:     bool,
+}
+
+impl<'a> Iterator for Fragments<'a> {
+	type Item = Packet<Vec<u8>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.position > 0 && self.position >= self.payload.len() {
+			return None;
+		}
+
+		let header = if self.position == 0 {
+			self.first
+		}
+		else {
+			&self.rest[..]
+		};
+
+		// Every fragment but the last must carry a multiple of 8 octets.
+		let available = self.payload.len() - self.position;
+		let length    = if header.len() + available <= self.mtu {
+			available
+		}
+		else {
+			(self.mtu - header.len()) / 8 * 8
+		};
+
+		let last = self.position + length == self.payload.len();
+
+		let mut buffer = Vec::with_capacity(header.len() + length);
+		buffer.extend_from_slice(header);
+		buffer.extend_from_slice(&self.payload[self.position .. self.position + length]);
+
+		Cursor::new(&mut buffer[2 ..])
+			.write_u16::<BigEndian>((header.len() + length) as u16).unwrap();
+
+		let mut packet = Packet::unchecked(buffer);
+		let mut flags  = packet.flags();
+		flags.set(Flags::MORE_FRAGMENTS, !last || self.more);
+
+		packet.set_flags(flags).unwrap();
+		packet.set_offset(self.offset + (self.position / 8) as u16).unwrap();
+		packet.update_checksum().unwrap();
+
+		// Make sure an empty payload still yields a single fragment.
+		self.position += length.max(1);
+
+		Some(packet)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::error::Error;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+
+	#[test]
+	fn fragment() {
+		let packet = ip::v4::Builder::default()
+			.id(0x1337).unwrap()
+			.ttl(64).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("10.0.0.1".parse().unwrap()).unwrap()
+			.record_route(1).unwrap()
+			.router_alert(0).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&(0 .. 100).collect::<Vec<u8>>()).unwrap()
+			.build().unwrap();
+
+		let packet    = ip::v4::Packet::new(packet).unwrap();
+		let fragments = ip::v4::fragment(&packet, 64).unwrap().collect::<Vec<_>>();
+		assert_eq!(fragments.len(), 3);
+
+		assert_eq!(fragments[0].header(), 8);
+		assert_eq!(fragments[0].length(), 64);
+		assert_eq!(fragments[0].offset(), 0);
+		assert!(fragments[0].flags().contains(ip::v4::flag::MORE_FRAGMENTS));
+		assert_eq!(fragments[0].options().count(), 2);
+
+		assert_eq!(fragments[1].header(), 6);
+		assert_eq!(fragments[1].length(), 64);
+		assert_eq!(fragments[1].offset(), 4);
+		assert!(fragments[1].flags().contains(ip::v4::flag::MORE_FRAGMENTS));
+
+		let options = fragments[1].options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 1);
+		assert_eq!(options[0].number(), ip::v4::option::Number::RouterAlert);
+
+		assert_eq!(fragments[2].header(), 6);
+		assert_eq!(fragments[2].length(), 52);
+		assert_eq!(fragments[2].offset(), 9);
+		assert!(!fragments[2].flags().contains(ip::v4::flag::MORE_FRAGMENTS));
+
+		let mut payload = Vec::new();
+		for fragment in &fragments {
+			assert!(fragment.is_valid());
+			assert_eq!(fragment.id(), 0x1337);
+			assert_eq!(fragment.protocol(), ip::Protocol::Udp);
+
+			payload.extend_from_slice(fragment.payload());
+		}
+
+		assert_eq!(payload, packet.payload());
+	}
+
+	#[test]
+	fn fits() {
+		let packet = ip::v4::Builder::default()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&[0; 100]).unwrap()
+			.build().unwrap();
+
+		let packet    = ip::v4::Packet::new(packet).unwrap();
+		let fragments = ip::v4::fragment(&packet, 1500).unwrap().collect::<Vec<_>>();
+		assert_eq!(fragments.len(), 1);
+		assert!(fragments[0].flags().is_empty());
+		assert_eq!(fragments[0].as_ref(), packet.as_ref());
+	}
+
+	#[test]
+	fn dont_fragment() {
+		let packet = ip::v4::Builder::default()
+			.flags(ip::v4::flag::DONT_FRAGMENT).unwrap()
+			.payload(&[0; 100]).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v4::Packet::new(packet).unwrap();
+		assert!(ip::v4::fragment(&packet, 64).is_err());
+	}
+
+	#[test]
+	fn malformed_options() {
+		let mut raw = vec![0x46u8, 0x00, 0x00, 0x7c, 0x00, 0x00, 0x00, 0x00, 0x40, 0x11, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00, 0x00, 0x02, 0x83, 0x00, 0x00, 0x00];
+		raw.extend_from_slice(&[0; 100]);
+
+		let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+		assert!(matches!(ip::v4::fragment(&packet, 68), Err(Error::InvalidPacket)));
+
+		raw[21] = 0x08;
+
+		let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+		assert!(matches!(ip::v4::fragment(&packet, 68), Err(Error::InvalidPacket)));
+	}
+
+	#[test]
+	fn short_header() {
+		let mut raw = vec![0x44u8, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x11, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00, 0x00, 0x02];
+		raw.resize(256, 0);
+
+		assert!(ip::v4::Packet::new(&raw[..]).is_err());
+
+		let packet = ip::v4::Packet::unchecked(&raw[..]);
+		assert!(matches!(ip::v4::fragment(&packet, 68), Err(Error::InvalidPacket)));
+	}
+}
@@ -26,6 +26,14 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+/// IPv4 fragmentation.
+pub mod fragment;
+pub use self::fragment::fragment;
+
+/// IPv4 fragment reassembly.
+pub mod reassembly;
+pub use self::reassembly::Reassembler;
+
 /// Calculate the checksum for an IPv4 packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
 	use std::io::Cursor;
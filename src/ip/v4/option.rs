@@ -13,6 +13,8 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, BigEndian};
 
 use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
@@ -133,6 +135,22 @@ pub enum Number {
 	Unknown(u8),
 }
 
+/// Timestamp option flag.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimestampFlag {
+	/// Only timestamps are recorded.
+	TimestampOnly,
+
+	/// Each timestamp is preceded by the address of the recording router.
+	WithAddress,
+
+	/// Only the prespecified routers record a timestamp.
+	Prespecified,
+
+	///
+	Unknown(u8),
+}
+
 impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.debug_struct("ip::v4::Option")
@@ -158,6 +176,22 @@ impl<B: AsRef<[u8]>> Option<B> {
 			Err(Error::SmallBuffer)?
 		}
 
+		match option.number() {
+			Number::End |
+			Number::NoOperation =>
+				(),
+
+			_ => {
+				if option.buffer.as_ref().len() < 2 {
+					Err(Error::SmallBuffer)?
+				}
+
+				if option.length() < 2 {
+					Err(Error::InvalidPacket)?
+				}
+			}
+		}
+
 		if option.buffer.as_ref().len() < option.length() as usize {
 			Err(Error::SmallBuffer)?
 		}
@@ -253,6 +287,281 @@ impl<B: AsRef<[u8]>> Option<B> {
 	}
 }
 
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Parse a Record Route, Loose Source Route or Strict Source Route option.
+	pub fn route(&self) -> Result<Route<&[u8]>> {
+		match self.number() {
+			Number::RecordRoute |
+			Number::LooseSourceRoute |
+			Number::StrictSourceRoute =>
+				Route::new(self.payload()),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+
+	/// Parse a Timestamp option.
+	pub fn timestamp(&self) -> Result<Timestamp<&[u8]>> {
+		if self.number() != Number::TimeStamp {
+			Err(Error::InvalidPacket)?
+		}
+
+		Timestamp::new(self.payload())
+	}
+
+	/// Value of a Router Alert option.
+	pub fn router_alert(&self) -> Result<u16> {
+		if self.number() != Number::RouterAlert {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((&self.payload()[..]).read_u16::<BigEndian>()?)
+	}
+
+	/// Parse a Security option.
+	pub fn security(&self) -> Result<Security<&[u8]>> {
+		if self.number() != Number::Security {
+			Err(Error::InvalidPacket)?
+		}
+
+		Security::new(self.payload())
+	}
+
+	/// Parse a Quick-Start option.
+	pub fn quick_start(&self) -> Result<QuickStart<&[u8]>> {
+		if self.number() != Number::QuickStart {
+			Err(Error::InvalidPacket)?
+		}
+
+		QuickStart::new(self.payload())
+	}
+}
+
+/// Record Route, Loose Source Route and Strict Source Route option parser.
+pub struct Route<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Route<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v4::option::Route")
+			.field("pointer", &self.pointer())
+			.field("addresses", &self.addresses().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Route<B> {
+	/// Parse a route option payload.
+	pub fn new(buffer: B) -> Result<Route<B>> {
+		if buffer.as_ref().is_empty() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Route { buffer })
+	}
+
+	/// Octet offset of the next address slot, counting from the start of the
+	/// option.
+	pub fn pointer(&self) -> u8 {
+		self.buffer.as_ref()[0]
+	}
+
+	/// Whether every address slot has been used.
+	pub fn is_full(&self) -> bool {
+		self.pointer() as usize > self.buffer.as_ref().len() + 2
+	}
+
+	/// All the addresses in the option.
+	pub fn addresses(&self) -> impl Iterator<Item = Ipv4Addr> + '_ {
+		self.buffer.as_ref()[1 ..].chunks_exact(4).map(|chunk|
+			Ipv4Addr::new(chunk[0], chunk[1], chunk[2], chunk[3]))
+	}
+
+	/// The addresses recorded so far, before the pointer.
+	pub fn recorded(&self) -> impl Iterator<Item = Ipv4Addr> + '_ {
+		self.addresses().take((self.pointer() as usize).saturating_sub(4) / 4)
+	}
+
+	/// The addresses left to visit, after the pointer.
+	pub fn remaining(&self) -> impl Iterator<Item = Ipv4Addr> + '_ {
+		self.addresses().skip((self.pointer() as usize).saturating_sub(4) / 4)
+	}
+}
+
+/// Timestamp option parser.
+pub struct Timestamp<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Timestamp<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v4::option::Timestamp")
+			.field("pointer", &self.pointer())
+			.field("overflow", &self.overflow())
+			.field("flag", &self.flag())
+			.field("entries", &self.entries().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Timestamp<B> {
+	/// Parse a Timestamp option payload.
+	pub fn new(buffer: B) -> Result<Timestamp<B>> {
+		if buffer.as_ref().len() < 2 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Timestamp { buffer })
+	}
+
+	/// Octet offset of the next entry, counting from the start of the option.
+	pub fn pointer(&self) -> u8 {
+		self.buffer.as_ref()[0]
+	}
+
+	/// Number of routers that couldn't record a timestamp for lack of space.
+	pub fn overflow(&self) -> u8 {
+		self.buffer.as_ref()[1] >> 4
+	}
+
+	/// What the entries contain.
+	pub fn flag(&self) -> TimestampFlag {
+		(self.buffer.as_ref()[1] & 0b1111).into()
+	}
+
+	/// All the entries in the option, as the address of the router if the
+	/// flag requires one and the timestamp.
+	pub fn entries(&self) -> impl Iterator<Item = (std::option::Option<Ipv4Addr>, u32)> + '_ {
+		let address = self.flag() != TimestampFlag::TimestampOnly;
+		let size    = if address { 8 } else { 4 };
+
+		self.buffer.as_ref()[2 ..].chunks_exact(size).map(move |chunk| {
+			if address {
+				(Some(Ipv4Addr::new(chunk[0], chunk[1], chunk[2], chunk[3])),
+					(&chunk[4 ..]).read_u32::<BigEndian>().unwrap())
+			}
+			else {
+				(None, (&chunk[..]).read_u32::<BigEndian>().unwrap())
+			}
+		})
+	}
+
+	/// The entries recorded so far, before the pointer.
+	pub fn recorded(&self) -> impl Iterator<Item = (std::option::Option<Ipv4Addr>, u32)> + '_ {
+		let size = if self.flag() != TimestampFlag::TimestampOnly { 8 } else { 4 };
+		self.entries().take((self.pointer() as usize).saturating_sub(5) / size)
+	}
+}
+
+/// Security option parser, as defined in RFC 1108.
+pub struct Security<B> {
+	buffer: B,
+}
+
+/// Security classification level.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Classification {
+	///
+	TopSecret,
+
+	///
+	Secret,
+
+	///
+	Confidential,
+
+	///
+	Unclassified,
+
+	///
+	Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Security<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v4::option::Security")
+			.field("classification", &self.classification())
+			.field("authority", &self.authority())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Security<B> {
+	/// Parse a Security option payload.
+	pub fn new(buffer: B) -> Result<Security<B>> {
+		if buffer.as_ref().is_empty() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Security { buffer })
+	}
+
+	/// Classification level.
+	pub fn classification(&self) -> Classification {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Protection authority flags, the low bit of each octet tells whether
+	/// another octet follows.
+	pub fn authority(&self) -> &[u8] {
+		&self.buffer.as_ref()[1 ..]
+	}
+}
+
+/// Quick-Start option parser.
+pub struct QuickStart<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for QuickStart<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v4::option::QuickStart")
+			.field("function", &self.function())
+			.field("rate", &self.rate())
+			.field("ttl", &self.ttl())
+			.field("nonce", &self.nonce())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> QuickStart<B> {
+	/// Parse a Quick-Start option payload.
+	pub fn new(buffer: B) -> Result<QuickStart<B>> {
+		if buffer.as_ref().len() < 6 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(QuickStart { buffer })
+	}
+
+	/// Function, 0 for a Rate Request and 8 for a Rate Report.
+	pub fn function(&self) -> u8 {
+		self.buffer.as_ref()[0] >> 4
+	}
+
+	/// Whether it's a Rate Report.
+	pub fn is_report(&self) -> bool {
+		self.function() == 8
+	}
+
+	/// Encoded rate, the rate in kbps is 40 * 2 ^ rate.
+	pub fn rate(&self) -> u8 {
+		self.buffer.as_ref()[0] & 0b1111
+	}
+
+	/// Quick-Start TTL, unused in reports.
+	pub fn ttl(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Quick-Start nonce.
+	pub fn nonce(&self) -> u32 {
+		(&self.buffer.as_ref()[2 ..]).read_u32::<BigEndian>().unwrap() >> 2
+	}
+}
+
 impl From<u8> for Class {
 	fn from(value: u8) -> Self {
 		use self::Class::*;
@@ -344,3 +653,131 @@ impl Into<u8> for Number {
 		}
 	}
 }
+
+impl From<u8> for TimestampFlag {
+	fn from(value: u8) -> Self {
+		use self::TimestampFlag::*;
+
+		match value {
+			0 => TimestampOnly,
+			1 => WithAddress,
+			3 => Prespecified,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for TimestampFlag {
+	fn into(self) -> u8 {
+		use self::TimestampFlag::*;
+
+		match self {
+			TimestampOnly => 0,
+			WithAddress   => 1,
+			Prespecified  => 3,
+			Unknown(n)    => n,
+		}
+	}
+}
+
+impl From<u8> for Classification {
+	fn from(value: u8) -> Self {
+		use self::Classification::*;
+
+		match value {
+			0x3d => TopSecret,
+			0x5a => Secret,
+			0x96 => Confidential,
+			0xab => Unclassified,
+			n    => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Classification {
+	fn into(self) -> u8 {
+		use self::Classification::*;
+
+		match self {
+			TopSecret    => 0x3d,
+			Secret       => 0x5a,
+			Confidential => 0x96,
+			Unclassified => 0xab,
+			Unknown(n)   => n,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::ip::v4::option::{self, Number, TimestampFlag, Classification};
+
+	#[test]
+	fn route() {
+		let raw = [0x07, 0x0b, 0x08, 10, 0, 0, 1, 0, 0, 0, 0];
+
+		let option = option::Option::new(&raw[..]).unwrap();
+		assert_eq!(option.number(), Number::RecordRoute);
+
+		let route = option.route().unwrap();
+		assert_eq!(route.pointer(), 8);
+		assert!(!route.is_full());
+		assert_eq!(route.recorded().collect::<Vec<_>>(), vec![Ipv4Addr::new(10, 0, 0, 1)]);
+		assert_eq!(route.remaining().collect::<Vec<_>>(), vec![Ipv4Addr::new(0, 0, 0, 0)]);
+
+		assert!(option.timestamp().is_err());
+	}
+
+	#[test]
+	fn timestamp() {
+		let raw = [
+			0x44, 0x14, 0x0d, 0x11,
+			10, 0, 0, 1, 0x00, 0x00, 0x12, 0x34,
+			0, 0, 0, 0, 0x00, 0x00, 0x00, 0x00];
+
+		let option = option::Option::new(&raw[..]).unwrap();
+		let timestamp = option.timestamp().unwrap();
+		assert_eq!(timestamp.overflow(), 1);
+		assert_eq!(timestamp.flag(), TimestampFlag::WithAddress);
+		assert_eq!(timestamp.recorded().collect::<Vec<_>>(), vec![(Some(Ipv4Addr::new(10, 0, 0, 1)), 0x1234)]);
+		assert_eq!(timestamp.entries().count(), 2);
+	}
+
+	#[test]
+	fn router_alert() {
+		let raw = [0x94, 0x04, 0x00, 0x00];
+		assert_eq!(option::Option::new(&raw[..]).unwrap().router_alert().unwrap(), 0);
+	}
+
+	#[test]
+	fn security() {
+		let raw = [0x82, 0x04, 0x5a, 0x80];
+
+		let option = option::Option::new(&raw[..]).unwrap();
+		let security = option.security().unwrap();
+		assert_eq!(security.classification(), Classification::Secret);
+		assert_eq!(security.authority(), &[0x80]);
+	}
+
+	#[test]
+	fn quick_start() {
+		let raw = [0x19, 0x08, 0x03, 0x40, 0x00, 0x00, 0x00, 0x04];
+
+		let option = option::Option::new(&raw[..]).unwrap();
+		let quick_start = option.quick_start().unwrap();
+		assert!(!quick_start.is_report());
+		assert_eq!(quick_start.rate(), 3);
+		assert_eq!(quick_start.ttl(), 0x40);
+		assert_eq!(quick_start.nonce(), 1);
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(option::Option::new(&[0x83u8, 0x00][..]).is_err());
+		assert!(option::Option::new(&[0x83u8, 0x01][..]).is_err());
+		assert!(option::Option::new(&[0x83u8][..]).is_err());
+		assert!(option::Option::new(&[0x83u8, 0x07, 0x04][..]).is_err());
+		assert!(option::Option::new(&[0x01u8][..]).is_ok());
+	}
+}
@@ -84,6 +84,10 @@ impl<B: AsRef<[u8]>> Packet<B> {
 			Err(Error::InvalidPacket)?
 		}
 
+		if packet.header() < 5 {
+			Err(Error::InvalidPacket)?
+		}
+
 		if packet.buffer.as_ref().len() < packet.header() as usize * 4 {
 			Err(Error::SmallBuffer)?
 		}
@@ -215,8 +219,8 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	/// Flags of the packet.
 	pub fn flags(&self) -> Flags {
-		Flags::from_bits((&self.buffer.as_ref()[6 ..])
-			.read_u16::<BigEndian>().unwrap() >> 13).unwrap()
+		Flags::from_bits_truncate((&self.buffer.as_ref()[6 ..])
+			.read_u16::<BigEndian>().unwrap() >> 13)
 	}
 
 	/// Offset of the packet.
@@ -305,16 +309,24 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 
 	/// Packet flags.
 	pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
+		let offset = self.offset();
+
 		Cursor::new(&mut self.header_mut()[6 ..])
-			.write_u16::<BigEndian>(value.bits())?;
+			.write_u16::<BigEndian>((value.bits() << 13) | offset)?;
 
 		Ok(self)
 	}
 
-	/// Packet fragment offset.
+	/// Packet fragment offset, in 8 octet units.
 	pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
+		if value > 0x1fff {
+			Err(Error::InvalidValue)?
+		}
+
+		let flags = self.flags();
+
 		Cursor::new(&mut self.header_mut()[6 ..])
-			.write_u16::<BigEndian>(value)?;
+			.write_u16::<BigEndian>((flags.bits() << 13) | value)?;
 
 		Ok(self)
 	}
@@ -465,8 +477,10 @@ impl<'a> Iterator for OptionIter<'a> {
 				Some(Ok(option))
 			}
 
-			Err(error) =>
+			Err(error) => {
+				self.buffer = &[];
 				Some(Err(error))
+			}
 		}
 	}
 }
@@ -480,7 +494,8 @@ mod test {
 	fn short_packet() {
 		assert!(ip::v4::Packet::no_payload(&[64; 10][..]).is_err());
 		assert!(ip::v4::Packet::no_payload(&[64; 19][..]).is_err());
-		assert!(ip::v4::Packet::no_payload(&[64; 20][..]).is_ok());
+		assert!(ip::v4::Packet::no_payload(&[0x45; 20][..]).is_ok());
+		assert!(ip::v4::Packet::no_payload(&[0x44; 20][..]).is_err());
 	}
 
 	#[test]
@@ -535,4 +550,22 @@ mod test {
 		ip.checked().set_id(0x4242).unwrap();
 		assert!(ip.is_valid());
 	}
+
+	#[test]
+	fn malformed_options() {
+		let raw = [0x46u8, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x00, 0x40, 0x11, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00, 0x00, 0x02, 0x83, 0x00, 0x00, 0x00];
+
+		let packet = ip::v4::Packet::unchecked(&raw[..]);
+		let mut options = packet.options();
+		assert!(options.next().unwrap().is_err());
+		assert!(options.next().is_none());
+
+		let mut raw = raw;
+		raw[20 ..].copy_from_slice(&[0x01, 0x01, 0x01, 0x83]);
+
+		let packet  = ip::v4::Packet::unchecked(&raw[..]);
+		let options = packet.options().collect::<Vec<_>>();
+		assert_eq!(options.len(), 4);
+		assert!(options[3].is_err());
+	}
 }
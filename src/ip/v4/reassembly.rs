@@ -0,0 +1,434 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::time::{Duration, Instant};
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::Protocol;
+use crate::ip::reassembly::Fragments;
+use crate::ip::v4::Packet;
+use crate::ip::v4::Flags;
+
+/// Identifies the datagram a fragment belongs to.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct Key {
+	/// Source address.
+	pub source: Ipv4Addr,
+
+	/// Destination address.
+	pub destination: Ipv4Addr,
+
+	/// Upper layer protocol.
+	pub protocol: Protocol,
+
+	/// Datagram identification.
+	pub id: u16,
+}
+
+impl Key {
+	/// Get the key of the datagram the packet belongs to.
+	pub fn new<B: AsRef<[u8]>>(packet: &Packet<B>) -> Key {
+		Key {
+			source:      packet.source(),
+			destination: packet.destination(),
+			protocol:    packet.protocol(),
+			id:          packet.id(),
+		}
+	}
+}
+
+/// Resource limits for the reassembler.
+#[derive(Copy, Clone, Debug)]
+pub struct Limits {
+	/// How long to wait for the missing fragments of a datagram, counting
+	/// from the first fragment received.
+	pub timeout: Duration,
+
+	/// Maximum number of bytes held for a single datagram.
+	pub flow: usize,
+
+	/// Maximum number of bytes held for all datagrams.
+	pub total: usize,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Limits {
+			timeout: Duration::from_secs(30),
+			flow:    0xffff,
+			total:   4 * 1024 * 1024,
+		}
+	}
+}
+
+/// IPv4 fragment reassembler.
+///
+/// Fragments are accepted in any order, exact duplicates are ignored, while
+/// any other overlap drops the whole datagram, in the spirit of RFC 5722.
+///
+/// Time never advances on its own, the caller passes the current time to
+/// every call so any clock can drive the timeouts.
+#[derive(Debug)]
+pub struct Reassembler {
+	limits: Limits,
+	flows:  HashMap<Key, Flow>,
+	memory: usize,
+}
+
+#[derive(Debug)]
+struct Flow {
+	started:   Instant,
+	header:    Option<Vec<u8>>,
+	fragments: Fragments,
+	memory:    usize,
+}
+
+impl Default for Reassembler {
+	fn default() -> Self {
+		Reassembler::new(Default::default())
+	}
+}
+
+impl Reassembler {
+	/// Create a reassembler with the given limits.
+	pub fn new(limits: Limits) -> Reassembler {
+		Reassembler {
+			limits: limits,
+			flows:  HashMap::new(),
+			memory: 0,
+		}
+	}
+
+	/// The resource limits.
+	pub fn limits(&self) -> &Limits {
+		&self.limits
+	}
+
+	/// Number of datagrams waiting for fragments.
+	pub fn len(&self) -> usize {
+		self.flows.len()
+	}
+
+	/// Check if no datagram is waiting for fragments.
+	pub fn is_empty(&self) -> bool {
+		self.flows.is_empty()
+	}
+
+	/// Number of bytes held for all datagrams.
+	pub fn memory(&self) -> usize {
+		self.memory
+	}
+
+	/// Insert a fragment, returning the reassembled datagram once all its
+	/// fragments have been received.
+	///
+	/// A packet that isn't a fragment is returned as is.
+	///
+	/// # Errors
+	///
+	/// An invalid or overlapping fragment, or one exceeding the per-datagram
+	/// limit, drops the datagram and returns `Error::InvalidPacket`, while a
+	/// fragment exceeding the global limit is dropped on its own and returns
+	/// `Error::SmallBuffer`.
+	pub fn insert<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>, now: Instant) -> Result<Option<Packet<Vec<u8>>>> {
+		let more   = packet.flags().contains(Flags::MORE_FRAGMENTS);
+		let offset = packet.offset() as usize * 8;
+
+		if !more && offset == 0 {
+			return Ok(Some(packet.to_owned()));
+		}
+
+		self.expire(now);
+
+		let key               = Key::new(packet);
+		let (header, payload) = packet.split();
+
+		// Every fragment but the last must carry a multiple of 8 octets, and
+		// the whole datagram must fit in the total length field.
+		if (more && (payload.is_empty() || payload.len() % 8 != 0)) ||
+		   header.len() + offset + payload.len() > 0xffff
+		{
+			self.remove(&key);
+			Err(Error::InvalidPacket)?
+		}
+
+		let fresh = match self.flows.get(&key) {
+			Some(flow) => flow.fragments.accepts(offset, payload, more),
+			None       => Ok(true),
+		};
+
+		match fresh {
+			Ok(true) =>
+				(),
+
+			Ok(false) =>
+				return Ok(None),
+
+			Err(error) => {
+				self.remove(&key);
+				Err(error)?
+			}
+		}
+
+		let size = payload.len() + if offset == 0 { header.len() } else { 0 };
+
+		if self.flows.get(&key).map_or(0, |flow| flow.memory) + size > self.limits.flow {
+			self.remove(&key);
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.memory + size > self.limits.total {
+			Err(Error::SmallBuffer)?
+		}
+
+		self.memory += size;
+
+		let flow = self.flows.entry(key).or_insert_with(|| Flow {
+			started:   now,
+			header:    None,
+			fragments: Default::default(),
+			memory:    0,
+		});
+
+		flow.memory += size;
+		flow.fragments.insert(offset, payload, more);
+
+		if offset == 0 {
+			flow.header = Some(header.to_vec());
+		}
+
+		if flow.header.is_none() || !flow.fragments.is_complete() {
+			return Ok(None);
+		}
+
+		let flow = self.flows.remove(&key).unwrap();
+		self.memory -= flow.memory;
+
+		flow.assemble().map(Some)
+	}
+
+	/// Drop the datagrams that timed out, returning their keys.
+	pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+		let timeout = self.limits.timeout;
+		let expired = self.flows.iter()
+			.filter(|(_, flow)| now.saturating_duration_since(flow.started) >= timeout)
+			.map(|(key, _)| *key)
+			.collect::<Vec<_>>();
+
+		for key in &expired {
+			self.remove(key);
+		}
+
+		expired
+	}
+
+	/// Drop a datagram and all its fragments.
+	pub fn remove(&mut self, key: &Key) -> bool {
+		if let Some(flow) = self.flows.remove(key) {
+			self.memory -= flow.memory;
+			true
+		}
+		else {
+			false
+		}
+	}
+}
+
+impl Flow {
+	/// Put the datagram back together.
+	fn assemble(self) -> Result<Packet<Vec<u8>>> {
+		let header = self.header.unwrap();
+		let length = header.len() + self.fragments.length().unwrap();
+
+		// The later fragments can carry a shorter header than the first one.
+		if length > 0xffff {
+			Err(Error::InvalidPacket)?
+		}
+
+		let mut buffer = Vec::with_capacity(length);
+		buffer.extend_from_slice(&header);
+		self.fragments.assemble(&mut buffer);
+
+		Cursor::new(&mut buffer[2 ..])
+			.write_u16::<BigEndian>(length as u16)?;
+
+		let mut packet = Packet::unchecked(buffer);
+		let mut flags  = packet.flags();
+		flags.remove(Flags::MORE_FRAGMENTS);
+
+		packet.set_flags(flags)?;
+		packet.set_offset(0)?;
+		packet.update_checksum()?;
+
+		Ok(packet)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::{Duration, Instant};
+	use crate::builder::Builder;
+	use crate::error::Error;
+	use crate::ip;
+	use crate::ip::v4::reassembly::{Reassembler, Limits};
+
+	fn packet() -> ip::v4::Packet<Vec<u8>> {
+		ip::v4::Packet::new(ip::v4::Builder::default()
+			.id(0x1337).unwrap()
+			.ttl(64).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("10.0.0.1".parse().unwrap()).unwrap()
+			.router_alert(0).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&(0 .. 100).collect::<Vec<u8>>()).unwrap()
+			.build().unwrap()).unwrap()
+	}
+
+	#[test]
+	fn reassemble() {
+		let packet    = packet();
+		let fragments = ip::v4::fragment(&packet, 64).unwrap().collect::<Vec<_>>();
+		assert_eq!(fragments.len(), 3);
+
+		let now     = Instant::now();
+		let mut set = Reassembler::default();
+
+		assert!(set.insert(&fragments[2], now).unwrap().is_none());
+		assert!(set.insert(&fragments[0], now).unwrap().is_none());
+		assert!(set.insert(&fragments[0], now).unwrap().is_none());
+		assert_eq!(set.len(), 1);
+
+		let result = set.insert(&fragments[1], now).unwrap().unwrap();
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+
+		assert!(result.is_valid());
+		assert_eq!(result.as_ref(), packet.as_ref());
+	}
+
+	#[test]
+	fn passthrough() {
+		let packet = packet();
+		let mut set = Reassembler::default();
+
+		let result = set.insert(&packet, Instant::now()).unwrap().unwrap();
+		assert_eq!(result.as_ref(), packet.as_ref());
+		assert!(set.is_empty());
+	}
+
+	#[test]
+	fn overlap() {
+		let packet    = packet();
+		let fragments = ip::v4::fragment(&packet, 64).unwrap().collect::<Vec<_>>();
+
+		let now     = Instant::now();
+		let mut set = Reassembler::default();
+
+		let mut evil = fragments[1].clone();
+		evil.set_offset(fragments[1].offset() + 1).unwrap();
+
+		assert!(set.insert(&fragments[0], now).unwrap().is_none());
+		assert!(set.insert(&fragments[1], now).unwrap().is_none());
+
+		match set.insert(&evil, now) {
+			Err(Error::InvalidPacket) => (),
+			result                    => panic!("unexpected {:?}", result),
+		}
+
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+	}
+
+	#[test]
+	fn timeout() {
+		let packet    = packet();
+		let fragments = ip::v4::fragment(&packet, 64).unwrap().collect::<Vec<_>>();
+
+		let now     = Instant::now();
+		let mut set = Reassembler::default();
+
+		for fragment in &fragments[.. 2] {
+			assert!(set.insert(fragment, now).unwrap().is_none());
+		}
+
+		assert!(set.expire(now + Duration::from_secs(10)).is_empty());
+
+		let later   = now + Duration::from_secs(30);
+		let expired = set.expire(later);
+		assert_eq!(expired.len(), 1);
+		assert_eq!(expired[0].id, 0x1337);
+		assert_eq!(expired[0].protocol, ip::Protocol::Udp);
+
+		assert!(set.insert(&fragments[2], later).unwrap().is_none());
+		assert_eq!(set.len(), 1);
+	}
+
+	#[test]
+	fn limits() {
+		let packet    = packet();
+		let fragments = ip::v4::fragment(&packet, 64).unwrap().collect::<Vec<_>>();
+		let now       = Instant::now();
+
+		let mut set = Reassembler::new(Limits { flow: 64, .. Default::default() });
+		assert!(set.insert(&fragments[0], now).unwrap().is_none());
+		assert!(set.insert(&fragments[1], now).is_err());
+		assert!(set.is_empty());
+
+		let mut set = Reassembler::new(Limits { total: 64, .. Default::default() });
+		assert!(set.insert(&fragments[0], now).unwrap().is_none());
+
+		match set.insert(&fragments[1], now) {
+			Err(Error::SmallBuffer) => (),
+			result                  => panic!("unexpected {:?}", result),
+		}
+
+		assert_eq!(set.len(), 1);
+	}
+
+	#[test]
+	fn oversized() {
+		fn fragment(options: usize, offset: usize, length: usize, more: bool) -> ip::v4::Packet<Vec<u8>> {
+			let header = 20 + options;
+			let total  = header + length;
+			let flags  = if more { 0x2000 } else { 0 } | (offset / 8) as u16;
+
+			let mut raw = vec![0x40 | (header / 4) as u8, 0x00, (total >> 8) as u8, total as u8,
+				0x13, 0x37, (flags >> 8) as u8, flags as u8, 0x40, 0x11, 0x00, 0x00,
+				0xc0, 0xa8, 0x00, 0x4f, 0x0a, 0x00, 0x00, 0x01];
+			raw.resize(header, 0x01);
+			raw.resize(total, 0x00);
+
+			ip::v4::Packet::unchecked(raw)
+		}
+
+		let now     = Instant::now();
+		let mut set = Reassembler::new(Limits { flow: 1024 * 1024, .. Default::default() });
+
+		assert!(set.insert(&fragment(40, 0, 1000, true), now).unwrap().is_none());
+		assert!(set.insert(&fragment(0, 1000, 32000, true), now).unwrap().is_none());
+		assert!(set.insert(&fragment(0, 33000, 32496, true), now).unwrap().is_none());
+
+		match set.insert(&fragment(0, 65496, 16, false), now) {
+			Err(Error::InvalidPacket) => (),
+			result                    => panic!("unexpected {:?}", result),
+		}
+
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+	}
+}
@@ -12,36 +12,685 @@
 //
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
-use crate::error::*;
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ip::Protocol;
+use crate::ip::v6::Packet;
+use crate::ip::v6::option;
+use crate::ip::v6::extension::{Fragment, RoutingType};
 
 /// IPv6 packet builder.
-#[allow(dead_code)]
+#[derive(Debug)]
 pub struct Builder<B: Buffer = buffer::Dynamic> {
-    buffer: B,
-    finalizer: Finalization,
+	buffer:    B,
+	finalizer: Finalization,
+
+	protocol: Protocol,
+	payload:  bool,
+
+	next:    usize,
+	options: Option<(Protocol, usize)>,
+	jumbo:   Option<usize>,
 }
 
 impl<B: Buffer> Build<B> for Builder<B> {
-    fn with(buffer: B) -> Result<Self> {
-        Ok(Builder {
-            buffer: buffer,
-            finalizer: Default::default(),
-        })
-    }
+	fn with(mut buffer: B) -> Result<Self> {
+		use crate::size::header::Min;
+		buffer.next(Packet::<()>::min())?;
+
+		// Set version to 6.
+		//
+		// XXX: The TCP and UDP builders base their finalizer on extracting the
+		//      parent IP packet, which checks the version.
+		buffer.data_mut()[0] = 6 << 4;
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+
+			protocol: Protocol::Ipv6NoNxt,
+			payload:  false,
+
+			next:    6,
+			options: None,
+			jumbo:   None,
+		})
+	}
 
-    fn finalizer(&mut self) -> &mut Finalization {
-        &mut self.finalizer
-    }
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
 
-    fn build(self) -> Result<B::Inner> {
-        Err(Error::InvalidPacket)
-    }
+	fn build(mut self) -> Result<B::Inner> {
+		self.prepare()?;
+
+		self.finalizer.build(self.buffer)
+	}
 }
 
 impl Default for Builder<buffer::Dynamic> {
-    fn default() -> Self {
-        Builder::with(buffer::Dynamic::default()).unwrap()
-    }
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+macro_rules! protocol {
+	($(#[$attr:meta])* fn $module:ident($protocol:ident)) => (
+		protocol!($(#[$attr])* fn $module($protocol) -> $module);
+	);
+
+	($(#[$attr:meta])* fn $name:ident($protocol:ident) -> $module:ident) => (
+		$(#[$attr])*
+		pub fn $name(mut self) -> Result<crate::$module::Builder<B>> {
+			if self.payload {
+				Err(Error::AlreadyDefined)?
+			}
+
+			self = self.next_header(Protocol::$protocol)?;
+			self.prepare()?;
+
+			let mut builder = crate::$module::Builder::with(self.buffer)?;
+			builder.finalizer().extend(self.finalizer);
+
+			Ok(builder)
+		}
+	)
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Traffic class.
+	pub fn traffic_class(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_traffic_class(value)?;
+		Ok(self)
+	}
+
+	/// Differentiated Services Code Point.
+	pub fn dscp(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_dscp(value)?;
+		Ok(self)
+	}
+
+	/// Explicit Congestion Notification.
+	pub fn ecn(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_ecn(value)?;
+		Ok(self)
+	}
+
+	/// Flow label.
+	pub fn flow_label(mut self, value: u32) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_flow_label(value)?;
+		Ok(self)
+	}
+
+	/// Hop limit.
+	pub fn hop_limit(mut self, value: u8) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_hop_limit(value)?;
+		Ok(self)
+	}
+
+	/// Source address.
+	pub fn source(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_source(value)?;
+		Ok(self)
+	}
+
+	/// Destination address.
+	pub fn destination(mut self, value: Ipv6Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
+		Ok(self)
+	}
+
+	/// Protocol of the payload.
+	pub fn next_header(mut self, value: Protocol) -> Result<Self> {
+		self.protocol = value;
+		Ok(self)
+	}
+
+	/// Add a Hop-by-Hop Options header, options are then added with
+	/// `option()` or the specific helpers.
+	pub fn hop_by_hop(mut self) -> Result<Self> {
+		// The Hop-by-Hop Options header must immediately follow the IPv6 header.
+		if self.next != 6 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let start = self.extension(Protocol::Hopopt, 2)?;
+		self.options = Some((Protocol::Hopopt, start));
+
+		Ok(self)
+	}
+
+	/// Add a Destination Options header, options are then added with
+	/// `option()`.
+	pub fn destination_options(mut self) -> Result<Self> {
+		let start = self.extension(Protocol::Ipv6Opts, 2)?;
+		self.options = Some((Protocol::Ipv6Opts, start));
+
+		Ok(self)
+	}
+
+	/// Add an option to the last options header, padding is inserted to
+	/// satisfy the alignment of known options and to terminate the header.
+	pub fn option(mut self, number: option::Number, value: &[u8]) -> Result<Self> {
+		let start = if let Some((_, start)) = self.options {
+			start
+		}
+		else {
+			Err(Error::InvalidPacket)?
+		};
+
+		if number == option::Number::Pad1 || value.len() > u8::max_value() as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		let (multiple, remainder) = alignment(number);
+		let position = self.buffer.length() - start;
+		self.pad((multiple + remainder - position % multiple) % multiple)?;
+
+		let position = self.buffer.length();
+		self.buffer.more(2 + value.len())?;
+
+		let data = &mut self.buffer.data_mut()[position ..];
+		data[0] = number.into();
+		data[1] = value.len() as u8;
+		data[2 ..].copy_from_slice(value);
+
+		Ok(self)
+	}
+
+	/// Add a Router Alert option to the Hop-by-Hop Options header.
+	pub fn router_alert(self, value: u16) -> Result<Self> {
+		match self.options {
+			Some((Protocol::Hopopt, _)) =>
+				self.option(option::Number::RouterAlert, &value.to_be_bytes()),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+
+	/// Add a Jumbo Payload option to the Hop-by-Hop Options header, the
+	/// length is filled in when the packet is built and the payload length
+	/// is set to zero.
+	pub fn jumbo_payload(mut self) -> Result<Self> {
+		if self.jumbo.is_some() {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self = match self.options {
+			Some((Protocol::Hopopt, _)) =>
+				self.option(option::Number::JumboPayload, &[0; 4])?,
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		};
+
+		self.jumbo = Some(self.buffer.length() - 4);
+
+		Ok(self)
+	}
+
+	/// Add a Segment Routing header, the segments are given in the order
+	/// they're visited and the destination address is set to the first one.
+	pub fn segment_routing<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(mut self, segments: T) -> Result<Self> {
+		let segments = segments.into_iter().collect::<Vec<_>>();
+
+		// The header length has to fit in 8 bits.
+		if segments.is_empty() || segments.len() > 127 {
+			Err(Error::InvalidValue)?
+		}
+
+		let start = self.extension(Protocol::Ipv6Route, 8 + segments.len() * 16)?;
+		let last  = (segments.len() - 1) as u8;
+
+		{
+			let data = &mut self.buffer.data_mut()[start ..];
+			data[1] = (segments.len() * 2) as u8;
+			data[2] = RoutingType::SegmentRouting.into();
+			data[3] = last;
+			data[4] = last;
+
+			// The segment list is encoded in reverse order.
+			for (index, segment) in segments.iter().rev().enumerate() {
+				data[8 + index * 16 .. 8 + (index + 1) * 16].copy_from_slice(&segment.octets());
+			}
+		}
+
+		Packet::unchecked(self.buffer.data_mut()).set_destination(*segments[0])?;
+
+		Ok(self)
+	}
+
+	/// Add a Fragment header.
+	pub fn fragment(mut self, offset: u16, more: bool, id: u32) -> Result<Self> {
+		let start = self.extension(Protocol::Ipv6Frag, 8)?;
+
+		Fragment::unchecked(&mut self.buffer.data_mut()[start ..])
+			.set_offset(offset)?
+			.set_more_fragments(more)?
+			.set_id(id)?;
+
+		Ok(self)
+	}
+
+	/// Payload for the packet.
+	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.close()?;
+		self.payload = true;
+
+		for byte in value {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+
+	/// Append an extension header of the given size, chaining it to the
+	/// previous header, and return its position in the layer.
+	fn extension(&mut self, protocol: Protocol, size: usize) -> Result<usize> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.close()?;
+
+		let start = self.buffer.length();
+		self.buffer.more(size)?;
+
+		let next = self.next;
+		self.buffer.data_mut()[next] = protocol.into();
+		self.next = start;
+
+		// Keep the payload length up to date so the extension headers can be
+		// parsed by the finalizers of the upper layers.
+		let length = self.buffer.length() - 40;
+		if length > u16::max_value() as usize {
+			Err(Error::InvalidPacket)?
+		}
+
+		Packet::unchecked(self.buffer.data_mut()).set_payload_length(length as u16)?;
+
+		Ok(start)
+	}
+
+	/// Pad the open options header to a multiple of 8 octets and write its
+	/// length.
+	fn close(&mut self) -> Result<()> {
+		if let Some((_, start)) = self.options.take() {
+			let length = self.buffer.length() - start;
+			self.pad((8 - length % 8) % 8)?;
+
+			let length = self.buffer.length() - start;
+			if length > 2048 {
+				Err(Error::InvalidPacket)?
+			}
+
+			self.buffer.data_mut()[start + 1] = (length / 8 - 1) as u8;
+
+			let length = self.buffer.length() - 40;
+			if length > u16::max_value() as usize {
+				Err(Error::InvalidPacket)?
+			}
+
+			Packet::unchecked(self.buffer.data_mut()).set_payload_length(length as u16)?;
+		}
+
+		Ok(())
+	}
+
+	/// Append a Pad1 or PadN option of the given size.
+	fn pad(&mut self, size: usize) -> Result<()> {
+		let position = self.buffer.length();
+
+		match size {
+			0 =>
+				(),
+
+			1 =>
+				self.buffer.more(1)?,
+
+			n => {
+				self.buffer.more(n)?;
+
+				let data = &mut self.buffer.data_mut()[position ..];
+				data[0] = option::Number::PadN.into();
+				data[1] = (n - 2) as u8;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn prepare(&mut self) -> Result<()> {
+		self.close()?;
+
+		// Write the protocol of the payload.
+		let next = self.next;
+		self.buffer.data_mut()[next] = self.protocol.into();
+
+		let offset = self.buffer.offset();
+		let jumbo  = self.jumbo;
+
+		self.finalizer.add(move |out| {
+			// Calculate and write the length of the payload.
+			let length = out.len() - (offset + 40);
+
+			if let Some(position) = jumbo {
+				if length > u32::max_value() as usize {
+					Err(Error::InvalidPacket)?
+				}
+
+				Cursor::new(&mut out[offset + 4 ..])
+					.write_u16::<BigEndian>(0)?;
+
+				Cursor::new(&mut out[offset + position ..])
+					.write_u32::<BigEndian>(length as u32)?;
+			}
+			else {
+				if length > u16::max_value() as usize {
+					Err(Error::InvalidPacket)?
+				}
+
+				Cursor::new(&mut out[offset + 4 ..])
+					.write_u16::<BigEndian>(length as u16)?;
+			}
+
+			Ok(())
+		});
+
+		Ok(())
+	}
+
+	protocol!(/// Build an ICMPv6 packet.
+		fn icmp(Ipv6Icmp) -> icmpv6);
+
+	/// Build an MLD packet, adding the Hop-by-Hop Options header with the
+	/// Router Alert option every MLD message must carry and setting the hop
+	/// limit to 1.
+	pub fn mld(self) -> Result<crate::icmpv6::Builder<B>> {
+		self.hop_limit(1)?
+			.hop_by_hop()?
+			.router_alert(crate::icmpv6::mld::ROUTER_ALERT)?
+			.icmp()
+	}
+
+	protocol!(/// Build a TCP packet.
+		fn tcp(Tcp));
+
+	protocol!(/// Build a UDP packet.
+		fn udp(Udp));
+}
+
+/// Alignment requirement of an option, as multiple and remainder.
+fn alignment(number: option::Number) -> (usize, usize) {
+	match number {
+		option::Number::RouterAlert  => (2, 0),
+		option::Number::QuickStart   => (4, 2),
+		option::Number::JumboPayload => (4, 2),
+		option::Number::HomeAddress  => (8, 6),
+		_                            => (1, 0),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ip;
+	use crate::tcp;
+	use crate::udp;
+
+	#[test]
+	fn payload() {
+		let packet = ip::v6::Builder::default()
+			.dscp(46).unwrap()
+			.flow_label(0x12345).unwrap()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.next_header(ip::Protocol::Udp).unwrap()
+			.payload(&[0xde, 0xad, 0xbe, 0xef]).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v6::Packet::new(packet).unwrap();
+
+		assert_eq!(packet.version(), 6);
+		assert_eq!(packet.dscp(), 46);
+		assert_eq!(packet.flow_label(), 0x12345);
+		assert_eq!(packet.payload_length(), 4);
+		assert_eq!(packet.next_header(), ip::Protocol::Udp);
+		assert_eq!(packet.hop_limit(), 64);
+		assert_eq!(packet.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(packet.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(packet.payload(), &[0xde, 0xad, 0xbe, 0xef]);
+	}
+
+	#[test]
+	fn slice() {
+		use crate::buffer;
+
+		let mut raw = [0u8; 64];
+
+		let packet = ip::v6::Builder::with(buffer::Slice::new(&mut raw[..])).unwrap()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.next_header(ip::Protocol::Udp).unwrap()
+			.payload(&[0xde, 0xad, 0xbe, 0xef]).unwrap()
+			.build().unwrap();
+
+		let packet = ip::v6::Packet::new(&packet[..]).unwrap();
+		assert_eq!(packet.payload_length(), 4);
+		assert_eq!(packet.payload(), &[0xde, 0xad, 0xbe, 0xef]);
+	}
+
+	#[test]
+	fn tcp() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.flags(tcp::flag::SYN).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.payload_length(), 23);
+		assert_eq!(ip.next_header(), ip::Protocol::Tcp);
+
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(tcp.source(), 1337);
+		assert_eq!(tcp.destination(), 9001);
+		assert_eq!(tcp.checksum(), 0x5399);
+		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn udp() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(53).unwrap()
+				.payload(b"hello").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.payload_length(), 13);
+		assert_eq!(ip.next_header(), ip::Protocol::Udp);
+
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(udp.checksum(), 0x5b1f);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn ether() {
+		let packet = ether::Builder::default()
+			.destination("33:33:00:00:00:01".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.ip().unwrap().v6().unwrap()
+				.hop_limit(255).unwrap()
+				.source("fe80::1".parse().unwrap()).unwrap()
+				.destination("ff02::1".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ether = ether::Packet::new(packet).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Ipv6);
+
+		let ip = ip::v6::Packet::new(ether.payload()).unwrap();
+		assert_eq!(ip.payload_length(), 0);
+		assert_eq!(ip.next_header(), ip::Protocol::Ipv6NoNxt);
+		assert_eq!(ip.hop_limit(), 255);
+	}
+
+	#[test]
+	fn hop_by_hop() {
+		let packet = ip::v6::Builder::default()
+			.source("fe80::1".parse().unwrap()).unwrap()
+			.destination("ff02::16".parse().unwrap()).unwrap()
+			.hop_by_hop().unwrap()
+				.router_alert(0).unwrap()
+			.next_header(ip::Protocol::Ipv6Icmp).unwrap()
+			.payload(&[0x8f, 0x00]).unwrap()
+			.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.payload_length(), 10);
+		assert_eq!(ip.next_header(), ip::Protocol::Hopopt);
+		assert_eq!(&ip.payload()[.. 8], &[0x3a, 0x00, 0x05, 0x02, 0x00, 0x00, 0x01, 0x00]);
+		assert_eq!(ip.upper_layer().unwrap(), (ip::Protocol::Ipv6Icmp, &[0x8f, 0x00][..]));
+	}
+
+	#[test]
+	fn jumbo_payload() {
+		let packet = ip::v6::Builder::default()
+			.hop_by_hop().unwrap()
+				.jumbo_payload().unwrap()
+			.next_header(ip::Protocol::Udp).unwrap()
+			.build().unwrap();
+
+		assert_eq!(&packet[4 .. 6], &[0x00, 0x00]);
+		assert_eq!(&packet[40 ..], &[0x11, 0x00, 0xc2, 0x04, 0x00, 0x00, 0x00, 0x08]);
+	}
+
+	#[test]
+	fn destination_options() {
+		let packet = ip::v6::Builder::default()
+			.destination_options().unwrap()
+				.option(ip::v6::option::Number::Unknown(0x1e), &[0xff]).unwrap()
+			.destination_options().unwrap()
+				.option(ip::v6::option::Number::Unknown(0x1e), &[1, 2, 3]).unwrap()
+			.build().unwrap();
+
+		assert_eq!(&packet[40 ..], &[
+			0x3c, 0x00, 0x1e, 0x01, 0xff, 0x01, 0x01, 0x00,
+			0x3b, 0x00, 0x1e, 0x03, 0x01, 0x02, 0x03, 0x00]);
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(extensions.len(), 2);
+	}
+
+	#[test]
+	fn options_without_header() {
+		assert!(ip::v6::Builder::default()
+			.option(ip::v6::option::Number::Unknown(0x1e), &[]).is_err());
+
+		assert!(ip::v6::Builder::default()
+			.destination_options().unwrap()
+			.router_alert(0).is_err());
+
+		assert!(ip::v6::Builder::default()
+			.fragment(0, false, 1).unwrap()
+			.hop_by_hop().is_err());
+	}
+
+	#[test]
+	fn segment_routing() {
+		let segments = [
+			"2001:db8::a".parse::<Ipv6Addr>().unwrap(),
+			"2001:db8::b".parse::<Ipv6Addr>().unwrap(),
+			"2001:db8::2".parse::<Ipv6Addr>().unwrap()];
+
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.segment_routing(&segments).unwrap()
+			.fragment(0, false, 0xdeadbeef).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(53).unwrap()
+				.payload(b"hello").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(packet).unwrap();
+		assert_eq!(ip.destination(), segments[0]);
+		assert_eq!(ip.final_destination(), segments[2]);
+		assert_eq!(ip.payload_length(), 8 + 48 + 8 + 13);
+
+		let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(extensions.len(), 2);
+
+		match extensions[0] {
+			ip::v6::Extension::Routing(ref header) => {
+				let srh = header.segment_routing().unwrap();
+				assert_eq!(srh.segments_left(), 2);
+				assert_eq!(srh.segments().collect::<Vec<_>>(),
+					segments.iter().rev().cloned().collect::<Vec<_>>());
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+
+		match extensions[1] {
+			ip::v6::Extension::Fragment(ref header) => {
+				assert_eq!(header.id(), 0xdeadbeef);
+				assert_eq!(header.next_header(), ip::Protocol::Udp);
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+
+		let (protocol, payload) = ip.upper_layer().unwrap();
+		assert_eq!(protocol, ip::Protocol::Udp);
+
+		// The checksum uses the final destination, so it matches the one
+		// without a routing header.
+		let udp = udp::Packet::new(payload).unwrap();
+		assert_eq!(udp.checksum(), 0x5b1f);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
 }
@@ -0,0 +1,119 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+
+/// Encapsulating Security Payload header parser.
+///
+/// # Note
+///
+/// Everything after the sequence number is encrypted, so the payload extends
+/// to the end of the buffer and the next header cannot be known.
+pub struct Esp<B> {
+	buffer: B,
+}
+
+sized!(Esp,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Esp<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Esp")
+			.field("spi", &self.spi())
+			.field("sequence", &self.sequence())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Esp<B> {
+	/// Create an ESP header without checking the buffer.
+	pub fn unchecked(buffer: B) -> Esp<B> {
+		Esp { buffer }
+	}
+
+	/// Parse an ESP header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Esp<B>> {
+		use crate::size::header::Min;
+
+		let header = Esp::unchecked(buffer);
+
+		if header.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Esp<B> {
+	fn as_ref(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Esp<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.buffer.as_mut()
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Esp<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Esp<&[u8]>> {
+		Esp::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Esp<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Esp<&mut [u8]>> {
+		Esp::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Esp<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(8)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Esp<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(8)
+	}
+}
+
+impl<B: AsRef<[u8]>> Esp<B> {
+	/// Security Parameters Index.
+	pub fn spi(&self) -> u32 {
+		(&self.buffer.as_ref()[0 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Sequence number.
+	pub fn sequence(&self) -> u32 {
+		(&self.buffer.as_ref()[4 ..]).read_u32::<BigEndian>().unwrap()
+	}
+}
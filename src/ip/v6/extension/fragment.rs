@@ -0,0 +1,172 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip::Protocol;
+
+/// Fragment header parser.
+pub struct Fragment<B> {
+	buffer: B,
+}
+
+sized!(Fragment,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Fragment<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Fragment")
+			.field("next_header", &self.next_header())
+			.field("offset", &self.offset())
+			.field("more_fragments", &self.more_fragments())
+			.field("id", &self.id())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+	/// Create a Fragment header without checking the buffer.
+	pub fn unchecked(buffer: B) -> Fragment<B> {
+		Fragment { buffer }
+	}
+
+	/// Parse a Fragment header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Fragment<B>> {
+		use crate::size::header::Min;
+
+		let header = Fragment::unchecked(buffer);
+
+		if header.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Fragment<B> {
+	fn as_ref(&self) -> &[u8] {
+		&self.buffer.as_ref()[.. 8]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Fragment<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.buffer.as_mut()[.. 8]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Fragment<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Fragment<&[u8]>> {
+		Fragment::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Fragment<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Fragment<&mut [u8]>> {
+		Fragment::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Fragment<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref()[.. 8].split_at(8)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Fragment<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut()[.. 8].split_at_mut(8)
+	}
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+	/// Protocol of the next header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Offset of the fragment in 8 octet units.
+	pub fn offset(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap() >> 3
+	}
+
+	/// Whether more fragments are waiting.
+	pub fn more_fragments(&self) -> bool {
+		self.buffer.as_ref()[3] & 1 == 1
+	}
+
+	/// Identification of the fragmented packet.
+	pub fn id(&self) -> u32 {
+		(&self.buffer.as_ref()[4 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Whether this is an atomic fragment, a fragment header on an
+	/// unfragmented packet.
+	pub fn is_atomic(&self) -> bool {
+		self.offset() == 0 && !self.more_fragments()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Fragment<B> {
+	/// Protocol of the next header.
+	pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+		self.buffer.as_mut()[0] = value.into();
+
+		Ok(self)
+	}
+
+	/// Offset of the fragment in 8 octet units.
+	pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
+		if value > 0x1fff {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = (&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>()?;
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>((old & 0b111) | value << 3)?;
+
+		Ok(self)
+	}
+
+	/// Whether more fragments are waiting.
+	pub fn set_more_fragments(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[3];
+		self.buffer.as_mut()[3] = (old & !1) | value as u8;
+
+		Ok(self)
+	}
+
+	/// Identification of the fragmented packet.
+	pub fn set_id(&mut self, value: u32) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[4 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
@@ -0,0 +1,389 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM};
+use crate::size;
+use crate::ip::Protocol;
+
+mod options;
+pub use self::options::{Options, OptionIter};
+
+mod routing;
+pub use self::routing::{Routing, RoutingType};
+
+mod segment_routing;
+pub use self::segment_routing::{SegmentRouting, SegmentIter};
+
+mod fragment;
+pub use self::fragment::Fragment;
+
+mod authentication;
+pub use self::authentication::Authentication;
+
+mod esp;
+pub use self::esp::Esp;
+
+/// Check if the protocol identifies an IPv6 extension header.
+pub fn is_extension(protocol: Protocol) -> bool {
+	match protocol {
+		Protocol::Hopopt |
+		Protocol::Ipv6Route |
+		Protocol::Ipv6Frag |
+		Protocol::Ipv6Opts |
+		Protocol::Ah |
+		Protocol::Esp =>
+			true,
+
+		_ =>
+			false
+	}
+}
+
+/// Generic IPv6 extension header.
+#[derive(Debug)]
+pub enum Extension<B: AsRef<[u8]>> {
+	/// Hop-by-Hop Options header.
+	HopByHop(Options<B>),
+
+	/// Routing header.
+	Routing(Routing<B>),
+
+	/// Fragment header.
+	Fragment(Fragment<B>),
+
+	/// Destination Options header.
+	DestinationOptions(Options<B>),
+
+	/// Authentication header.
+	Authentication(Authentication<B>),
+
+	/// Encapsulating Security Payload header.
+	Esp(Esp<B>),
+}
+
+impl<B: AsRef<[u8]>> Extension<B> {
+	/// Parse the extension header identified by the given protocol.
+	pub fn new(protocol: Protocol, buffer: B) -> Result<Extension<B>> {
+		match protocol {
+			Protocol::Hopopt =>
+				Options::new(buffer).map(Extension::HopByHop),
+
+			Protocol::Ipv6Route =>
+				Routing::new(buffer).map(Extension::Routing),
+
+			Protocol::Ipv6Frag =>
+				Fragment::new(buffer).map(Extension::Fragment),
+
+			Protocol::Ipv6Opts =>
+				Options::new(buffer).map(Extension::DestinationOptions),
+
+			Protocol::Ah =>
+				Authentication::new(buffer).map(Extension::Authentication),
+
+			Protocol::Esp =>
+				Esp::new(buffer).map(Extension::Esp),
+
+			_ =>
+				Err(Error::InvalidPacket)
+		}
+	}
+
+	/// Protocol identifying the extension header.
+	pub fn protocol(&self) -> Protocol {
+		match *self {
+			Extension::HopByHop(_)           => Protocol::Hopopt,
+			Extension::Routing(_)            => Protocol::Ipv6Route,
+			Extension::Fragment(_)           => Protocol::Ipv6Frag,
+			Extension::DestinationOptions(_) => Protocol::Ipv6Opts,
+			Extension::Authentication(_)     => Protocol::Ah,
+			Extension::Esp(_)                => Protocol::Esp,
+		}
+	}
+
+	/// Protocol of the next header, `None` for ESP since it's encrypted.
+	pub fn next_header(&self) -> Option<Protocol> {
+		match *self {
+			Extension::HopByHop(ref header) |
+			Extension::DestinationOptions(ref header) =>
+				Some(header.next_header()),
+
+			Extension::Routing(ref header) =>
+				Some(header.next_header()),
+
+			Extension::Fragment(ref header) =>
+				Some(header.next_header()),
+
+			Extension::Authentication(ref header) =>
+				Some(header.next_header()),
+
+			Extension::Esp(_) =>
+				None,
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Extension<B> {
+	fn as_ref(&self) -> &[u8] {
+		match *self {
+			Extension::HopByHop(ref header) |
+			Extension::DestinationOptions(ref header) =>
+				header.as_ref(),
+
+			Extension::Routing(ref header) =>
+				header.as_ref(),
+
+			Extension::Fragment(ref header) =>
+				header.as_ref(),
+
+			Extension::Authentication(ref header) =>
+				header.as_ref(),
+
+			Extension::Esp(ref header) =>
+				header.as_ref(),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Extension<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		match *self {
+			Extension::HopByHop(ref mut header) |
+			Extension::DestinationOptions(ref mut header) =>
+				header.as_mut(),
+
+			Extension::Routing(ref mut header) =>
+				header.as_mut(),
+
+			Extension::Fragment(ref mut header) =>
+				header.as_mut(),
+
+			Extension::Authentication(ref mut header) =>
+				header.as_mut(),
+
+			Extension::Esp(ref mut header) =>
+				header.as_mut(),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Extension<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		match *self {
+			Extension::HopByHop(ref header) |
+			Extension::DestinationOptions(ref header) =>
+				header.split(),
+
+			Extension::Routing(ref header) =>
+				header.split(),
+
+			Extension::Fragment(ref header) =>
+				header.split(),
+
+			Extension::Authentication(ref header) =>
+				header.split(),
+
+			Extension::Esp(ref header) =>
+				header.split(),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Extension<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		match *self {
+			Extension::HopByHop(ref mut header) |
+			Extension::DestinationOptions(ref mut header) =>
+				header.split_mut(),
+
+			Extension::Routing(ref mut header) =>
+				header.split_mut(),
+
+			Extension::Fragment(ref mut header) =>
+				header.split_mut(),
+
+			Extension::Authentication(ref mut header) =>
+				header.split_mut(),
+
+			Extension::Esp(ref mut header) =>
+				header.split_mut(),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> size::header::Size for Extension<B> {
+	fn size(&self) -> usize {
+		self.header().len()
+	}
+}
+
+impl<B: AsRef<[u8]>> size::payload::Size for Extension<B> {
+	fn size(&self) -> usize {
+		self.payload().len()
+	}
+}
+
+/// Iterator over IPv6 extension headers.
+pub struct ExtensionIter<'a> {
+	protocol: Option<Protocol>,
+	buffer:   &'a [u8],
+}
+
+impl<'a> ExtensionIter<'a> {
+	pub(in crate::ip::v6) fn new(protocol: Protocol, buffer: &'a [u8]) -> Self {
+		ExtensionIter {
+			protocol: Some(protocol),
+			buffer:   buffer,
+		}
+	}
+}
+
+impl<'a> Iterator for ExtensionIter<'a> {
+	type Item = Result<Extension<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use crate::size::Size;
+
+		let protocol = match self.protocol {
+			Some(protocol) if is_extension(protocol) =>
+				protocol,
+
+			_ =>
+				return None
+		};
+
+		match Extension::new(protocol, self.buffer) {
+			Ok(extension) => {
+				self.protocol = extension.next_header();
+				self.buffer   = &self.buffer[extension.size() ..];
+
+				Some(Ok(extension))
+			}
+
+			Err(error) => {
+				self.protocol = None;
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::ip::v6::extension::{Extension, RoutingType};
+	use crate::ip::v6::option;
+
+	const RAW: [u8; 108] = [
+		// IPv6
+		0x60, 0x00, 0x00, 0x00, 0x00, 0x44, 0x00, 0x40,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+		// Hop-by-Hop, Router Alert and PadN
+		0x2b, 0x00, 0x05, 0x02, 0x00, 0x00, 0x01, 0x00,
+		// Segment Routing
+		0x2c, 0x04, 0x04, 0x01, 0x01, 0x00, 0x00, 0x2a,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+		// Fragment
+		0x3c, 0x00, 0x00, 0x00, 0xde, 0xad, 0xbe, 0xef,
+		// Destination Options, Pad1 and PadN
+		0x06, 0x00, 0x00, 0x01, 0x03, 0x00, 0x00, 0x00,
+		// TCP
+		0x05, 0x39, 0x23, 0x29];
+
+	#[test]
+	fn chain() {
+		let ip = ip::v6::Packet::new(&RAW[..]).unwrap();
+		let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+
+		assert_eq!(extensions.len(), 4);
+
+		match extensions[0] {
+			Extension::HopByHop(ref header) => {
+				let options = header.options().collect::<Result<Vec<_>, _>>().unwrap();
+
+				assert_eq!(options.len(), 2);
+				assert_eq!(options[0].number(), option::Number::RouterAlert);
+				assert_eq!(options[0].payload(), &[0x00, 0x00]);
+				assert_eq!(options[1].number(), option::Number::PadN);
+				assert_eq!(options[1].length(), 0);
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+
+		match extensions[1] {
+			Extension::Routing(ref header) => {
+				assert_eq!(header.kind(), RoutingType::SegmentRouting);
+				assert_eq!(header.segments_left(), 1);
+
+				let srh = header.segment_routing().unwrap();
+				assert_eq!(srh.tag(), 42);
+				assert_eq!(srh.segments().collect::<Vec<_>>(), vec![
+					"2001:db8::3".parse::<Ipv6Addr>().unwrap(),
+					"2001:db8::2".parse::<Ipv6Addr>().unwrap()]);
+				assert_eq!(srh.active(), Some("2001:db8::2".parse().unwrap()));
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+
+		match extensions[2] {
+			Extension::Fragment(ref header) => {
+				assert_eq!(header.offset(), 0);
+				assert!(!header.more_fragments());
+				assert!(header.is_atomic());
+				assert_eq!(header.id(), 0xdeadbeef);
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+
+		match extensions[3] {
+			Extension::DestinationOptions(ref header) => {
+				assert_eq!(header.next_header(), ip::Protocol::Tcp);
+				assert_eq!(header.options().count(), 2);
+			}
+
+			ref other =>
+				panic!("unexpected {:?}", other),
+		}
+	}
+
+	#[test]
+	fn upper_layer() {
+		let ip = ip::v6::Packet::new(&RAW[..]).unwrap();
+		let (protocol, payload) = ip.upper_layer().unwrap();
+
+		assert_eq!(protocol, ip::Protocol::Tcp);
+		assert_eq!(payload, &[0x05, 0x39, 0x23, 0x29]);
+	}
+
+	#[test]
+	fn truncated() {
+		let mut raw = RAW;
+		raw[41] = 0xff;
+
+		let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ip.extensions().count(), 1);
+		assert!(ip.extensions().next().unwrap().is_err());
+		assert!(ip.upper_layer().is_err());
+	}
+}
@@ -0,0 +1,206 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip::Protocol;
+use crate::ip::v6::extension::SegmentRouting;
+
+/// Routing header parser.
+pub struct Routing<B> {
+	buffer: B,
+}
+
+sized!(Routing,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  4,
+		max:  2044,
+		size: p => p.length() as usize * 8 + 4,
+	});
+
+/// Routing header types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum RoutingType {
+	/// Deprecated source route.
+	SourceRoute,
+
+	///
+	Nimrod,
+
+	/// Mobile IPv6 home address.
+	Type2,
+
+	///
+	Rpl,
+
+	///
+	SegmentRouting,
+
+	///
+	Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Routing<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Routing")
+			.field("next_header", &self.next_header())
+			.field("length", &self.length())
+			.field("kind", &self.kind())
+			.field("segments_left", &self.segments_left())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+	/// Create a Routing header without checking the buffer.
+	pub fn unchecked(buffer: B) -> Routing<B> {
+		Routing { buffer }
+	}
+
+	/// Parse a Routing header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Routing<B>> {
+		use crate::size::Min;
+		use crate::size::Size;
+
+		let header = Routing::unchecked(buffer);
+
+		if header.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if header.buffer.as_ref().len() < header.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Routing<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Routing<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Routing<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Routing<&[u8]>> {
+		Routing::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Routing<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Routing<&mut [u8]>> {
+		Routing::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Routing<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(4)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Routing<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(4)
+	}
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+	/// Protocol of the next header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the header in 8 octet units, not including the first 8
+	/// octets.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Routing type.
+	pub fn kind(&self) -> RoutingType {
+		self.buffer.as_ref()[2].into()
+	}
+
+	/// Number of route segments remaining.
+	pub fn segments_left(&self) -> u8 {
+		self.buffer.as_ref()[3]
+	}
+
+	/// Parse a Segment Routing header.
+	pub fn segment_routing(&self) -> Result<SegmentRouting<&B>> {
+		SegmentRouting::new(&self.buffer)
+	}
+
+	/// Parse a Segment Routing header.
+	pub fn segment_routing_mut(&mut self) -> Result<SegmentRouting<&mut B>> {
+		SegmentRouting::new(&mut self.buffer)
+	}
+}
+
+impl From<u8> for RoutingType {
+	fn from(value: u8) -> Self {
+		use self::RoutingType::*;
+
+		match value {
+			0 => SourceRoute,
+			1 => Nimrod,
+			2 => Type2,
+			3 => Rpl,
+			4 => SegmentRouting,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for RoutingType {
+	fn into(self) -> u8 {
+		use self::RoutingType::*;
+
+		match self {
+			SourceRoute    => 0,
+			Nimrod         => 1,
+			Type2          => 2,
+			Rpl            => 3,
+			SegmentRouting => 4,
+			Unknown(n)     => n,
+		}
+	}
+}
@@ -0,0 +1,250 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip::Protocol;
+use crate::ip::v6::extension::RoutingType;
+
+/// Segment Routing header parser.
+pub struct SegmentRouting<B> {
+	buffer: B,
+}
+
+sized!(SegmentRouting,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  2040,
+		size: p => p.length() as usize * 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for SegmentRouting<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::SegmentRouting")
+			.field("next_header", &self.next_header())
+			.field("length", &self.length())
+			.field("segments_left", &self.segments_left())
+			.field("last_entry", &self.last_entry())
+			.field("flags", &self.flags())
+			.field("tag", &self.tag())
+			.field("segments", &self.segments().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> SegmentRouting<B> {
+	/// Create a Segment Routing header without checking the buffer.
+	pub fn unchecked(buffer: B) -> SegmentRouting<B> {
+		SegmentRouting { buffer }
+	}
+
+	/// Parse a Segment Routing header, checking the buffer contents are
+	/// correct.
+	pub fn new(buffer: B) -> Result<SegmentRouting<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let header = SegmentRouting::unchecked(buffer);
+
+		if header.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if RoutingType::from(header.buffer.as_ref()[2]) != RoutingType::SegmentRouting {
+			Err(Error::InvalidPacket)?
+		}
+
+		if header.buffer.as_ref().len() < header.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if (header.last_entry() as usize + 1) * 16 > header.length() as usize * 8 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for SegmentRouting<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for SegmentRouting<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, SegmentRouting<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<SegmentRouting<&[u8]>> {
+		SegmentRouting::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, SegmentRouting<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<SegmentRouting<&mut [u8]>> {
+		SegmentRouting::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for SegmentRouting<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(8)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for SegmentRouting<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(8)
+	}
+}
+
+impl<B: AsRef<[u8]>> SegmentRouting<B> {
+	/// Protocol of the next header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the header in 8 octet units, not including the first 8
+	/// octets.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Index of the next segment to visit.
+	pub fn segments_left(&self) -> u8 {
+		self.buffer.as_ref()[3]
+	}
+
+	/// Index of the last element of the segment list.
+	pub fn last_entry(&self) -> u8 {
+		self.buffer.as_ref()[4]
+	}
+
+	/// Header flags.
+	pub fn flags(&self) -> u8 {
+		self.buffer.as_ref()[5]
+	}
+
+	/// Tag of the packet.
+	pub fn tag(&self) -> u16 {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Segment at the given index, the segment list is encoded in reverse
+	/// order so the first index is the final destination.
+	pub fn segment(&self, index: u8) -> Option<Ipv6Addr> {
+		if index > self.last_entry() {
+			return None;
+		}
+
+		let offset = 8 + index as usize * 16;
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[offset .. offset + 16]);
+
+		Some(Ipv6Addr::from(octets))
+	}
+
+	/// Segment being currently visited.
+	pub fn active(&self) -> Option<Ipv6Addr> {
+		self.segment(self.segments_left())
+	}
+
+	/// Segment list, in encoding order.
+	pub fn segments(&self) -> SegmentIter<'_> {
+		let length = (self.last_entry() as usize + 1) * 16;
+
+		SegmentIter {
+			buffer: &self.buffer.as_ref()[8 .. 8 + length],
+		}
+	}
+
+	/// Type-length-value objects following the segment list.
+	pub fn tlvs(&self) -> &[u8] {
+		&self.payload()[(self.last_entry() as usize + 1) * 16 ..]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> SegmentRouting<B> {
+	/// Number of segments left.
+	pub fn set_segments_left(&mut self, value: u8) -> Result<&mut Self> {
+		if value > self.last_entry() {
+			Err(Error::InvalidValue)?
+		}
+
+		self.buffer.as_mut()[3] = value;
+
+		Ok(self)
+	}
+
+	/// Header flags.
+	pub fn set_flags(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[5] = value;
+
+		Ok(self)
+	}
+
+	/// Tag of the packet.
+	pub fn set_tag(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
+
+/// Iterator over a segment list.
+pub struct SegmentIter<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for SegmentIter<'a> {
+	type Item = Ipv6Addr;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.len() < 16 {
+			return None;
+		}
+
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer[.. 16]);
+		self.buffer = &self.buffer[16 ..];
+
+		Some(Ipv6Addr::from(octets))
+	}
+}
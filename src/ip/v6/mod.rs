@@ -17,3 +17,15 @@ pub use self::packet::Packet;
 
 mod builder;
 pub use self::builder::Builder;
+
+/// IPv6 option parser.
+pub mod option;
+pub use self::option::Option;
+
+/// IPv6 extension header parsers.
+pub mod extension;
+pub use self::extension::Extension;
+
+/// IPv6 fragment reassembly.
+pub mod reassembly;
+pub use self::reassembly::Reassembler;
@@ -0,0 +1,249 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+
+/// IPv6 Hop-by-Hop and Destination option parser.
+pub struct Option<B> {
+	buffer: B,
+}
+
+sized!(Option,
+	header {
+		min: 1,
+		max: 2,
+		size: p => match p.number() {
+			Number::Pad1 => 1,
+			_            => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  255,
+		size: p => p.length() as usize,
+	});
+
+/// Action to take when the option is not recognized.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Action {
+	/// Skip over the option.
+	Skip,
+
+	/// Discard the packet.
+	Discard,
+
+	/// Discard the packet and send an ICMP Parameter Problem.
+	DiscardIcmp,
+
+	/// Discard the packet and send an ICMP Parameter Problem if the destination
+	/// was not multicast.
+	DiscardIcmpUnicast,
+}
+
+/// IPv6 option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+	///
+	Pad1,
+
+	///
+	PadN,
+
+	///
+	TunnelEncapsulationLimit,
+
+	///
+	RouterAlert,
+
+	///
+	Calipso,
+
+	///
+	QuickStart,
+
+	///
+	JumboPayload,
+
+	///
+	HomeAddress,
+
+	///
+	Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::Option")
+			.field("action", &self.action())
+			.field("is_mutable", &self.is_mutable())
+			.field("number", &self.number())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Parse an IPv6 option, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Option<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let option = Option {
+			buffer: buffer,
+		};
+
+		if option.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if option.number() != Number::Pad1 && option.buffer.as_ref().len() < 2 {
+			Err(Error::SmallBuffer)?
+		}
+
+		if option.buffer.as_ref().len() < option.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(option)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Option<&[u8]>> {
+		Option::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+		Option::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		match self.number() {
+			Number::Pad1 =>
+				self.buffer.as_ref()[.. 1].split_at(1),
+
+			_ =>
+				self.buffer.as_ref()[.. 2 + self.length() as usize].split_at(2),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		match self.number() {
+			Number::Pad1 =>
+				self.buffer.as_mut()[.. 1].split_at_mut(1),
+
+			_ => {
+				let length = self.length() as usize;
+				self.buffer.as_mut()[.. 2 + length].split_at_mut(2)
+			}
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Action to take if the option is not recognized.
+	pub fn action(&self) -> Action {
+		match self.buffer.as_ref()[0] >> 6 {
+			0 => Action::Skip,
+			1 => Action::Discard,
+			2 => Action::DiscardIcmp,
+			_ => Action::DiscardIcmpUnicast,
+		}
+	}
+
+	/// Whether the option data may change en route.
+	pub fn is_mutable(&self) -> bool {
+		(self.buffer.as_ref()[0] >> 5) & 1 == 1
+	}
+
+	/// Option number.
+	pub fn number(&self) -> Number {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the option data in octets.
+	pub fn length(&self) -> u8 {
+		match self.number() {
+			Number::Pad1 =>
+				0,
+
+			_ =>
+				self.buffer.as_ref()[1]
+		}
+	}
+}
+
+impl From<u8> for Number {
+	fn from(value: u8) -> Self {
+		use self::Number::*;
+
+		match value {
+			0x00 => Pad1,
+			0x01 => PadN,
+			0x04 => TunnelEncapsulationLimit,
+			0x05 => RouterAlert,
+			0x07 => Calipso,
+			0x26 => QuickStart,
+			0xc2 => JumboPayload,
+			0xc9 => HomeAddress,
+			n    => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Number {
+	fn into(self) -> u8 {
+		use self::Number::*;
+
+		match self {
+			Pad1                     => 0x00,
+			PadN                     => 0x01,
+			TunnelEncapsulationLimit => 0x04,
+			RouterAlert              => 0x05,
+			Calipso                  => 0x07,
+			QuickStart               => 0x26,
+			JumboPayload             => 0xc2,
+			HomeAddress              => 0xc9,
+			Unknown(n)               => n,
+		}
+	}
+}
@@ -13,31 +13,48 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
 use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip::Protocol;
+use crate::ip::v6::option;
+use crate::ip::v6::extension::{self, Extension, ExtensionIter, RoutingType};
 
 /// IPv6 packet parser.
-#[derive(Clone)]
+#[derive(Copy, Clone)]
 pub struct Packet<B> {
 	buffer: B,
 }
 
 sized!(Packet,
 	header {
-		min:  0,
-		max:  0,
-		size: 0,
+		min:  40,
+		max:  40,
+		size: 40,
 	}
 
 	payload {
 		min:  0,
-		max:  0,
-		size: 0,
+		max:  u16::max_value() as usize,
+		size: p => p.payload_length() as usize,
 	});
 
 impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.debug_struct("ip::v6::Packet")
+			.field("version", &self.version())
+			.field("dscp", &self.dscp())
+			.field("ecn", &self.ecn())
+			.field("flow_label", &self.flow_label())
+			.field("payload_length", &self.payload_length())
+			.field("next_header", &self.next_header())
+			.field("hop_limit", &self.hop_limit())
+			.field("source", &self.source())
+			.field("destination", &self.destination())
+			.field("payload", &self.payload())
 			.finish()
 	}
 }
@@ -67,7 +84,15 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	/// Parse an IPv6 packet, checking the buffer contents are correct.
 	pub fn new(buffer: B) -> Result<Packet<B>> {
-		Packet::no_payload(buffer)
+		use crate::size::header::Min;
+
+		let packet = Packet::no_payload(buffer)?;
+
+		if packet.buffer.as_ref().len() < Self::min() + packet.payload_length() as usize {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
 	}
 }
 
@@ -85,13 +110,18 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
 	fn as_ref(&self) -> &[u8] {
-		&[]
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
 	}
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
 	fn as_mut(&mut self) -> &mut [u8] {
-		&mut []
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
 	}
 }
 
@@ -109,12 +139,373 @@ impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for
 
 impl<B: AsRef<[u8]>> P for Packet<B> {
 	fn split(&self) -> (&[u8], &[u8]) {
-		(&[], &[])
+		use crate::size::header::Size as HeaderSize;
+		use crate::size::payload::Size as PayloadSize;
+
+		let header  = HeaderSize::size(self);
+		let payload = PayloadSize::size(self);
+
+		let buffer = self.buffer.as_ref();
+		let buffer = if buffer.len() < header + payload {
+			buffer
+		}
+		else {
+			&buffer[.. header + payload]
+		};
+
+		buffer.split_at(header)
 	}
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
 	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
-		(&mut [], &mut [])
+		use crate::size::header::Size as HeaderSize;
+		use crate::size::payload::Size as PayloadSize;
+
+		let header  = HeaderSize::size(self);
+		let payload = PayloadSize::size(self);
+
+		let buffer = self.buffer.as_mut();
+		let buffer = if buffer.len() < header + payload {
+			buffer
+		}
+		else {
+			&mut buffer[.. header + payload]
+		};
+
+		buffer.split_at_mut(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// IP protocol version, will always be 6.
+	pub fn version(&self) -> u8 {
+		self.buffer.as_ref()[0] >> 4
+	}
+
+	/// Traffic class, made of the DSCP and ECN values.
+	pub fn traffic_class(&self) -> u8 {
+		(self.buffer.as_ref()[0] << 4) | (self.buffer.as_ref()[1] >> 4)
+	}
+
+	/// DSCP value.
+	pub fn dscp(&self) -> u8 {
+		self.traffic_class() >> 2
+	}
+
+	/// ECN value.
+	pub fn ecn(&self) -> u8 {
+		self.traffic_class() & 0b11
+	}
+
+	/// Flow label of the packet.
+	pub fn flow_label(&self) -> u32 {
+		(&self.buffer.as_ref()[0 ..]).read_u32::<BigEndian>().unwrap() & 0xf_ffff
+	}
+
+	/// Length of the payload in octets, extension headers included.
+	pub fn payload_length(&self) -> u16 {
+		(&self.buffer.as_ref()[4 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Protocol of the header following the IPv6 header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[6].into()
+	}
+
+	/// Hop limit for the packet.
+	pub fn hop_limit(&self) -> u8 {
+		self.buffer.as_ref()[7]
+	}
+
+	/// Source IP address.
+	pub fn source(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[8 .. 24]);
+
+		Ipv6Addr::from(octets)
+	}
+
+	/// Destination IP address.
+	pub fn destination(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[24 .. 40]);
+
+		Ipv6Addr::from(octets)
+	}
+
+	/// Final destination of the packet, which is the last address of the
+	/// routing header if there is one with segments left.
+	pub fn final_destination(&self) -> Ipv6Addr {
+		for extension in self.extensions() {
+			let header = match extension {
+				Ok(Extension::Routing(header)) =>
+					header,
+
+				Ok(_) =>
+					continue,
+
+				Err(_) =>
+					break,
+			};
+
+			// The destination is already the final one, RFC 8200 section 8.1.
+			if header.segments_left() == 0 {
+				break;
+			}
+
+			match header.kind() {
+				RoutingType::SegmentRouting => {
+					if let Some(address) = header.segment_routing().ok().and_then(|h| h.segment(0)) {
+						return address;
+					}
+				}
+
+				RoutingType::SourceRoute | RoutingType::Type2 => {
+					// Addresses follow 4 reserved octets.
+					let addresses = &header.payload()[4 ..];
+
+					if addresses.len() >= 16 {
+						let end = addresses.len() / 16 * 16;
+
+						let mut octets = [0u8; 16];
+						octets.copy_from_slice(&addresses[end - 16 .. end]);
+
+						return Ipv6Addr::from(octets);
+					}
+				}
+
+				_ =>
+					(),
+			}
+		}
+
+		self.destination()
+	}
+
+	/// Extension headers following the IPv6 header.
+	pub fn extensions(&self) -> ExtensionIter<'_> {
+		ExtensionIter::new(self.next_header(), self.payload())
+	}
+
+	/// Value of the Router Alert option in the Hop-by-Hop Options header, if
+	/// any.
+	pub fn router_alert(&self) -> Option<u16> {
+		let header = match self.extensions().next() {
+			Some(Ok(Extension::HopByHop(header))) =>
+				header,
+
+			_ =>
+				return None,
+		};
+
+		for option in header.options() {
+			let option = option.ok()?;
+
+			if option.number() == option::Number::RouterAlert && option.length() == 2 {
+				return Some((&option.payload()[..]).read_u16::<BigEndian>().unwrap());
+			}
+		}
+
+		None
+	}
+
+	/// Upper-layer protocol and its data, after walking the extension
+	/// header chain.
+	///
+	/// # Note
+	///
+	/// For ESP the returned data starts at the ESP header, and for fragments
+	/// with a non-zero offset it's the fragment data and not an upper-layer
+	/// header.
+	pub fn upper_layer(&self) -> Result<(Protocol, &[u8])> {
+		use crate::size::Size;
+
+		let mut protocol = self.next_header();
+		let mut buffer   = self.payload();
+
+		while extension::is_extension(protocol) && protocol != Protocol::Esp {
+			let header = Extension::new(protocol, buffer)?;
+
+			protocol = header.next_header().unwrap();
+			buffer   = &buffer[header.size() ..];
+		}
+
+		Ok((protocol, buffer))
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Traffic class.
+	pub fn set_traffic_class(&mut self, value: u8) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[0];
+		self.buffer.as_mut()[0] = (old & 0xf0) | (value >> 4);
+
+		let old = self.buffer.as_ref()[1];
+		self.buffer.as_mut()[1] = (old & 0x0f) | (value << 4);
+
+		Ok(self)
+	}
+
+	/// Differentiated Services Code Point.
+	pub fn set_dscp(&mut self, value: u8) -> Result<&mut Self> {
+		if value > 0b11_1111 {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = self.traffic_class();
+		self.set_traffic_class((old & 0b11) | value << 2)
+	}
+
+	/// Explicit Congestion Notification.
+	pub fn set_ecn(&mut self, value: u8) -> Result<&mut Self> {
+		if value > 0b11 {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = self.traffic_class();
+		self.set_traffic_class((old & 0b1111_1100) | value)
+	}
+
+	/// Flow label.
+	pub fn set_flow_label(&mut self, value: u32) -> Result<&mut Self> {
+		if value > 0xf_ffff {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = (&self.buffer.as_ref()[0 ..]).read_u32::<BigEndian>()?;
+		Cursor::new(&mut self.buffer.as_mut()[0 ..])
+			.write_u32::<BigEndian>((old & 0xfff0_0000) | value)?;
+
+		Ok(self)
+	}
+
+	/// Payload length.
+	pub fn set_payload_length(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[4 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Next header protocol.
+	pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+		self.buffer.as_mut()[6] = value.into();
+
+		Ok(self)
+	}
+
+	/// Hop limit.
+	pub fn set_hop_limit(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[7] = value;
+
+		Ok(self)
+	}
+
+	/// Source address.
+	pub fn set_source(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[8 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Destination address.
+	pub fn set_destination(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[24 .. 40].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::packet::Packet;
+	use crate::ip;
+
+	const RAW: [u8; 44] = [
+		0x6b, 0x81, 0x23, 0x45, 0x00, 0x04, 0x11, 0x40,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+		0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+		0xde, 0xad, 0xbe, 0xef];
+
+	#[test]
+	fn short_packet() {
+		assert!(ip::v6::Packet::no_payload(&[0x60; 10][..]).is_err());
+		assert!(ip::v6::Packet::no_payload(&[0x60; 39][..]).is_err());
+		assert!(ip::v6::Packet::no_payload(&[0x60; 40][..]).is_ok());
+		assert!(ip::v6::Packet::no_payload(&[0x40; 40][..]).is_err());
+		assert!(ip::v6::Packet::new(&RAW[.. 43]).is_err());
+	}
+
+	#[test]
+	fn values() {
+		let ip = ip::v6::Packet::new(&RAW[..]).unwrap();
+
+		assert_eq!(ip.version(), 6);
+		assert_eq!(ip.traffic_class(), 0xb8);
+		assert_eq!(ip.dscp(), 46);
+		assert_eq!(ip.ecn(), 0);
+		assert_eq!(ip.flow_label(), 0x12345);
+		assert_eq!(ip.payload_length(), 4);
+		assert_eq!(ip.next_header(), ip::Protocol::Udp);
+		assert_eq!(ip.hop_limit(), 64);
+		assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(ip.payload(), &[0xde, 0xad, 0xbe, 0xef]);
+	}
+
+	#[test]
+	fn generic() {
+		let ip = ip::Packet::new(&RAW[..]).unwrap();
+
+		assert_eq!(ip.header().len(), 40);
+		assert_eq!(ip.payload(), &[0xde, 0xad, 0xbe, 0xef]);
+	}
+
+	#[test]
+	fn mutable() {
+		let mut raw = RAW;
+		let mut ip  = ip::v6::Packet::new(&mut raw[..]).unwrap();
+
+		ip.set_dscp(10).unwrap();
+		ip.set_ecn(3).unwrap();
+		ip.set_flow_label(0xabcde).unwrap();
+		ip.set_hop_limit(1).unwrap();
+		ip.set_next_header(ip::Protocol::Tcp).unwrap();
+		ip.set_destination("fe80::1".parse().unwrap()).unwrap();
+
+		assert_eq!(ip.version(), 6);
+		assert_eq!(ip.dscp(), 10);
+		assert_eq!(ip.ecn(), 3);
+		assert_eq!(ip.flow_label(), 0xabcde);
+		assert_eq!(ip.hop_limit(), 1);
+		assert_eq!(ip.next_header(), ip::Protocol::Tcp);
+		assert_eq!(ip.destination(), "fe80::1".parse::<Ipv6Addr>().unwrap());
+		assert!(ip.set_flow_label(0x10_0000).is_err());
+	}
+
+	#[test]
+	fn final_destination() {
+		use crate::builder::Builder;
+
+		let segments = [
+			"2001:db8::a".parse::<Ipv6Addr>().unwrap(),
+			"2001:db8::2".parse::<Ipv6Addr>().unwrap()];
+
+		let mut packet = ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.segment_routing(&segments).unwrap()
+			.next_header(ip::Protocol::Udp).unwrap()
+			.build().unwrap();
+
+		assert_eq!(ip::v6::Packet::new(&packet[..]).unwrap().final_destination(), segments[1]);
+
+		// Once no segment is left the destination is the final one.
+		packet[43] = 0;
+
+		let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ip.destination(), segments[0]);
+		assert_eq!(ip.final_destination(), segments[0]);
 	}
 }
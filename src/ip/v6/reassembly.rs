@@ -0,0 +1,535 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::collections::HashMap;
+use std::net::Ipv6Addr;
+use std::time::{Duration, Instant};
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::builder::Builder as Build;
+use crate::ip::Protocol;
+use crate::ip::reassembly::Fragments;
+use crate::ip::v6::Packet;
+use crate::ip::v6::extension::{self, Extension, Fragment};
+use crate::icmpv6::code;
+
+/// Identifies the datagram a fragment belongs to.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct Key {
+	/// Source address.
+	pub source: Ipv6Addr,
+
+	/// Destination address.
+	pub destination: Ipv6Addr,
+
+	/// Fragment identification.
+	pub id: u32,
+}
+
+/// Resource limits for the reassembler.
+#[derive(Copy, Clone, Debug)]
+pub struct Limits {
+	/// How long to wait for the missing fragments of a datagram, counting
+	/// from the first fragment received.
+	pub timeout: Duration,
+
+	/// Maximum number of bytes held for a single datagram.
+	pub flow: usize,
+
+	/// Maximum number of bytes held for all datagrams.
+	pub total: usize,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Limits {
+			timeout: Duration::from_secs(60),
+			flow:    0xffff + 40,
+			total:   4 * 1024 * 1024,
+		}
+	}
+}
+
+/// A datagram dropped because its fragments didn't arrive in time.
+#[derive(Debug)]
+pub struct Expired {
+	/// Key of the datagram.
+	pub key: Key,
+
+	first: Option<Vec<u8>>,
+}
+
+impl Expired {
+	/// The first fragment, if it was received.
+	pub fn first(&self) -> Option<&[u8]> {
+		self.first.as_ref().map(|first| &first[..])
+	}
+
+	/// Build the ICMPv6 Time Exceeded message to send back to the source.
+	///
+	/// As per RFC 8200 the message is only sent if the first fragment was
+	/// received, otherwise `None` is returned.
+	pub fn time_exceeded(&self) -> Result<Option<Vec<u8>>> {
+		let first = match self.first {
+			Some(ref first) => first,
+			None            => return Ok(None),
+		};
+
+		Ok(Some(crate::ip::v6::Builder::default()
+			.hop_limit(64)?
+			.source(self.key.destination)?
+			.destination(self.key.source)?
+			.icmp()?.previous()?
+				.time_exceeded(code::TimeExceeded::FragmentReassemblyTimeExceeded)?
+				.packet(first)?
+				.build()?))
+	}
+}
+
+/// IPv6 fragment reassembler.
+///
+/// Fragments are accepted in any order, exact duplicates are ignored, while
+/// any other overlap drops the whole datagram as per RFC 5722. Atomic
+/// fragments are handled in isolation as per RFC 6946.
+///
+/// Time never advances on its own, the caller passes the current time to
+/// every call so any clock can drive the timeouts, and should call `expire`
+/// periodically to send the Time Exceeded messages.
+#[derive(Debug)]
+pub struct Reassembler {
+	limits: Limits,
+	flows:  HashMap<Key, Flow>,
+	memory: usize,
+}
+
+#[derive(Debug)]
+struct Flow {
+	started:   Instant,
+	header:    Option<(usize, Vec<u8>)>,
+	fragments: Fragments,
+	memory:    usize,
+}
+
+impl Default for Reassembler {
+	fn default() -> Self {
+		Reassembler::new(Default::default())
+	}
+}
+
+impl Reassembler {
+	/// Create a reassembler with the given limits.
+	pub fn new(limits: Limits) -> Reassembler {
+		Reassembler {
+			limits: limits,
+			flows:  HashMap::new(),
+			memory: 0,
+		}
+	}
+
+	/// The resource limits.
+	pub fn limits(&self) -> &Limits {
+		&self.limits
+	}
+
+	/// Number of datagrams waiting for fragments.
+	pub fn len(&self) -> usize {
+		self.flows.len()
+	}
+
+	/// Check if no datagram is waiting for fragments.
+	pub fn is_empty(&self) -> bool {
+		self.flows.is_empty()
+	}
+
+	/// Number of bytes held for all datagrams.
+	pub fn memory(&self) -> usize {
+		self.memory
+	}
+
+	/// Insert a fragment, returning the reassembled datagram once all its
+	/// fragments have been received.
+	///
+	/// The reassembled datagram has no Fragment header, and a packet without
+	/// one is returned as is.
+	///
+	/// # Errors
+	///
+	/// An invalid or overlapping fragment, or one exceeding the per-datagram
+	/// limit, drops the datagram and returns `Error::InvalidPacket`, while a
+	/// fragment exceeding the global limit is dropped on its own and returns
+	/// `Error::SmallBuffer`.
+	pub fn insert<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>, now: Instant) -> Result<Option<Packet<Vec<u8>>>> {
+		let buffer = packet.as_ref();
+
+		let (field, position) = match locate(packet)? {
+			Some(value) => value,
+			None        => return Ok(Some(packet.to_owned())),
+		};
+
+		let fragment = Fragment::new(&buffer[position ..])?;
+		let header   = &buffer[.. position];
+		let payload  = &buffer[position + 8 ..];
+		let offset   = fragment.offset() as usize * 8;
+		let more     = fragment.more_fragments();
+
+		if fragment.is_atomic() {
+			return strip(header, field, fragment.next_header(), payload).map(Some);
+		}
+
+		let key = Key {
+			source:      packet.source(),
+			destination: packet.destination(),
+			id:          fragment.id(),
+		};
+
+		let timeout = self.limits.timeout;
+
+		if self.flows.get(&key).is_some_and(|flow| now.saturating_duration_since(flow.started) >= timeout) {
+			self.remove(&key);
+		}
+
+		// The later fragments can carry a shorter unfragmentable part than the
+		// first one, which is the one that ends up in the datagram.
+		let unfragmentable = self.flows.get(&key)
+			.and_then(|flow| flow.header.as_ref())
+			.map_or(header.len(), |(_, first)| first.len() - 8);
+
+		// Every fragment but the last must carry a multiple of 8 octets, and
+		// the whole datagram must fit in the payload length field.
+		if (more && (payload.is_empty() || payload.len() % 8 != 0)) ||
+		   unfragmentable.max(header.len()) - 40 + offset + payload.len() > 0xffff
+		{
+			self.remove(&key);
+			Err(Error::InvalidPacket)?
+		}
+
+		let fresh = match self.flows.get(&key) {
+			Some(flow) => flow.fragments.accepts(offset, payload, more),
+			None       => Ok(true),
+		};
+
+		match fresh {
+			Ok(true) =>
+				(),
+
+			Ok(false) =>
+				return Ok(None),
+
+			Err(error) => {
+				self.remove(&key);
+				Err(error)?
+			}
+		}
+
+		let size = payload.len() + if offset == 0 { header.len() + 8 } else { 0 };
+
+		if self.flows.get(&key).map_or(0, |flow| flow.memory) + size > self.limits.flow {
+			self.remove(&key);
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.memory + size > self.limits.total {
+			Err(Error::SmallBuffer)?
+		}
+
+		self.memory += size;
+
+		let flow = self.flows.entry(key).or_insert_with(|| Flow {
+			started:   now,
+			header:    None,
+			fragments: Default::default(),
+			memory:    0,
+		});
+
+		flow.memory += size;
+		flow.fragments.insert(offset, payload, more);
+
+		if offset == 0 {
+			flow.header = Some((field, buffer[.. position + 8].to_vec()));
+		}
+
+		if flow.header.is_none() || !flow.fragments.is_complete() {
+			return Ok(None);
+		}
+
+		let flow = self.flows.remove(&key).unwrap();
+		self.memory -= flow.memory;
+
+		flow.assemble().map(Some)
+	}
+
+	/// Drop the datagrams that timed out.
+	pub fn expire(&mut self, now: Instant) -> Vec<Expired> {
+		let timeout = self.limits.timeout;
+		let keys    = self.flows.iter()
+			.filter(|(_, flow)| now.saturating_duration_since(flow.started) >= timeout)
+			.map(|(key, _)| *key)
+			.collect::<Vec<_>>();
+
+		let mut expired = Vec::with_capacity(keys.len());
+
+		for key in keys {
+			let flow = self.flows.remove(&key).unwrap();
+			self.memory -= flow.memory;
+
+			expired.push(Expired {
+				key:   key,
+				first: flow.first(),
+			});
+		}
+
+		expired
+	}
+
+	/// Drop a datagram and all its fragments.
+	pub fn remove(&mut self, key: &Key) -> bool {
+		if let Some(flow) = self.flows.remove(key) {
+			self.memory -= flow.memory;
+			true
+		}
+		else {
+			false
+		}
+	}
+}
+
+impl Flow {
+	/// Rebuild the first fragment as it was received.
+	fn first(&self) -> Option<Vec<u8>> {
+		let (_, header) = self.header.as_ref()?;
+		let data        = self.fragments.get(0)?;
+
+		if header.len() + data.len() - 40 > 0xffff {
+			return None;
+		}
+
+		let mut buffer = Vec::with_capacity(header.len() + data.len());
+		buffer.extend_from_slice(header);
+		buffer.extend_from_slice(data);
+
+		Cursor::new(&mut buffer[4 ..])
+			.write_u16::<BigEndian>((header.len() + data.len() - 40) as u16).unwrap();
+
+		Some(buffer)
+	}
+
+	/// Put the datagram back together.
+	fn assemble(self) -> Result<Packet<Vec<u8>>> {
+		let (field, header) = self.header.unwrap();
+		let position        = header.len() - 8;
+		let next            = Fragment::unchecked(&header[position ..]).next_header();
+
+		let mut data = Vec::with_capacity(self.fragments.length().unwrap());
+		self.fragments.assemble(&mut data);
+
+		strip(&header[.. position], field, next, &data)
+	}
+}
+
+/// Find the Fragment header, returning the offset of the field pointing to
+/// it and the offset of the header itself.
+fn locate<B: AsRef<[u8]>>(packet: &Packet<B>) -> Result<Option<(usize, usize)>> {
+	use crate::size::Size;
+
+	let buffer = packet.as_ref();
+
+	let mut protocol = packet.next_header();
+	let mut field    = 6;
+	let mut position = 40;
+
+	while extension::is_extension(protocol) && protocol != Protocol::Esp {
+		if protocol == Protocol::Ipv6Frag {
+			return Ok(Some((field, position)));
+		}
+
+		let header = Extension::new(protocol, &buffer[position ..])?;
+
+		protocol  = header.next_header().unwrap();
+		field     = position;
+		position += header.size();
+	}
+
+	Ok(None)
+}
+
+/// Build a packet out of the unfragmentable part and the data, without a
+/// Fragment header.
+fn strip(header: &[u8], field: usize, next: Protocol, data: &[u8]) -> Result<Packet<Vec<u8>>> {
+	let length = header.len() + data.len() - 40;
+
+	if length > 0xffff {
+		Err(Error::InvalidPacket)?
+	}
+
+	let mut buffer = Vec::with_capacity(header.len() + data.len());
+	buffer.extend_from_slice(header);
+	buffer.extend_from_slice(data);
+	buffer[field] = next.into();
+
+	Cursor::new(&mut buffer[4 ..])
+		.write_u16::<BigEndian>(length as u16)?;
+
+	Packet::new(buffer)
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::{Duration, Instant};
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::error::Error;
+	use crate::ip;
+	use crate::icmpv6;
+	use crate::ip::v6::Reassembler;
+	use crate::ip::v6::reassembly::Limits;
+
+	fn fragment(offset: u16, more: bool, data: &[u8]) -> ip::v6::Packet<Vec<u8>> {
+		ip::v6::Packet::new(ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.hop_by_hop().unwrap()
+			.router_alert(0).unwrap()
+			.fragment(offset, more, 0xdeadbeef).unwrap()
+			.next_header(ip::Protocol::Udp).unwrap()
+			.payload(data).unwrap()
+			.build().unwrap()).unwrap()
+	}
+
+	#[test]
+	fn reassemble() {
+		let data = (0 .. 100).collect::<Vec<u8>>();
+		let now  = Instant::now();
+
+		let mut set = Reassembler::default();
+		assert!(set.insert(&fragment(12, false, &data[96 ..]), now).unwrap().is_none());
+		assert!(set.insert(&fragment(0, true, &data[.. 48]), now).unwrap().is_none());
+		assert!(set.insert(&fragment(0, true, &data[.. 48]), now).unwrap().is_none());
+		assert_eq!(set.len(), 1);
+
+		let packet = set.insert(&fragment(6, true, &data[48 .. 96]), now).unwrap().unwrap();
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+
+		assert_eq!(packet.payload_length(), 108);
+		assert_eq!(packet.router_alert(), Some(0));
+		assert_eq!(packet.extensions().count(), 1);
+		assert_eq!(packet.upper_layer().unwrap(), (ip::Protocol::Udp, &data[..]));
+	}
+
+	#[test]
+	fn atomic() {
+		let mut set = Reassembler::default();
+		let packet  = set.insert(&fragment(0, false, b"test"), Instant::now()).unwrap().unwrap();
+
+		assert!(set.is_empty());
+		assert_eq!(packet.payload_length(), 12);
+		assert_eq!(packet.upper_layer().unwrap(), (ip::Protocol::Udp, &b"test"[..]));
+	}
+
+	#[test]
+	fn overlap() {
+		let data = (0 .. 100).collect::<Vec<u8>>();
+		let now  = Instant::now();
+
+		let mut set = Reassembler::default();
+		assert!(set.insert(&fragment(0, true, &data[.. 48]), now).unwrap().is_none());
+
+		match set.insert(&fragment(4, false, &data[32 ..]), now) {
+			Err(Error::InvalidPacket) => (),
+			result                    => panic!("unexpected {:?}", result),
+		}
+
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+	}
+
+	#[test]
+	fn time_exceeded() {
+		let data = (0 .. 100).collect::<Vec<u8>>();
+		let now  = Instant::now();
+
+		let mut set = Reassembler::default();
+		assert!(set.insert(&fragment(0, true, &data[.. 48]), now).unwrap().is_none());
+		assert!(set.expire(now + Duration::from_secs(30)).is_empty());
+
+		let expired = set.expire(now + Duration::from_secs(60));
+		assert_eq!(expired.len(), 1);
+		assert_eq!(expired[0].key.id, 0xdeadbeef);
+		assert_eq!(expired[0].first().unwrap(), fragment(0, true, &data[.. 48]).as_ref());
+		assert!(set.is_empty());
+
+		let packet = ip::v6::Packet::new(expired[0].time_exceeded().unwrap().unwrap()).unwrap();
+		assert_eq!(packet.source(), "2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap());
+		assert_eq!(packet.destination(), "2001:db8::1".parse::<std::net::Ipv6Addr>().unwrap());
+
+		let icmp = icmpv6::Packet::new(packet.payload()).unwrap();
+		assert!(icmp.is_valid(&packet));
+		assert_eq!(icmp.kind(), icmpv6::Kind::TimeExceeded);
+		assert_eq!(icmpv6::code::TimeExceeded::from(icmp.code()),
+			icmpv6::code::TimeExceeded::FragmentReassemblyTimeExceeded);
+
+		assert!(set.insert(&fragment(6, false, &data[48 ..]), now).unwrap().is_none());
+
+		let expired = set.expire(now + Duration::from_secs(60));
+		assert_eq!(expired.len(), 1);
+		assert!(expired[0].time_exceeded().unwrap().is_none());
+	}
+
+	#[test]
+	fn oversized() {
+		fn short(offset: u16, more: bool, data: &[u8]) -> ip::v6::Packet<Vec<u8>> {
+			ip::v6::Packet::new(ip::v6::Builder::default()
+				.hop_limit(64).unwrap()
+				.source("2001:db8::1".parse().unwrap()).unwrap()
+				.destination("2001:db8::2".parse().unwrap()).unwrap()
+				.fragment(offset, more, 0xdeadbeef).unwrap()
+				.next_header(ip::Protocol::Udp).unwrap()
+				.payload(data).unwrap()
+				.build().unwrap()).unwrap()
+		}
+
+		// Only the first fragment carries a Hop-by-Hop header, which makes the
+		// datagram 8 bytes too long.
+		let data  = vec![0u8; 65528];
+		let now   = Instant::now();
+		let limit = Limits { flow: 1024 * 1024, .. Default::default() };
+
+		let mut set = Reassembler::new(limit);
+		assert!(set.insert(&fragment(0, true, &data[.. 1000]), now).unwrap().is_none());
+		assert!(set.insert(&short(125, true, &data[1000 .. 33000]), now).unwrap().is_none());
+		assert!(set.insert(&short(4125, true, &data[33000 .. 65496]), now).unwrap().is_none());
+
+		match set.insert(&short(8187, false, &data[65496 ..]), now) {
+			Err(Error::InvalidPacket) => (),
+			result                    => panic!("unexpected {:?}", result),
+		}
+
+		assert!(set.is_empty());
+
+		let mut set = Reassembler::new(limit);
+		assert!(set.insert(&short(125, true, &data[1000 .. 33000]), now).unwrap().is_none());
+		assert!(set.insert(&short(4125, true, &data[33000 .. 65496]), now).unwrap().is_none());
+		assert!(set.insert(&short(8187, false, &data[65496 ..]), now).unwrap().is_none());
+
+		match set.insert(&fragment(0, true, &data[.. 1000]), now) {
+			Err(Error::InvalidPacket) => (),
+			result                    => panic!("unexpected {:?}", result),
+		}
+
+		assert!(set.is_empty());
+		assert_eq!(set.memory(), 0);
+	}
+}
@@ -42,6 +42,12 @@ pub mod ip;
 /// ICMP packet parser and builder.
 pub mod icmp;
 
+/// ICMPv6 packet parser and builder.
+pub mod icmpv6;
+
+/// IGMP packet parser and builder.
+pub mod igmp;
+
 /// TCP packet parser and builder.
 pub mod tcp;
 
@@ -50,3 +56,6 @@ pub mod udp;
 
 /// ARP packet parser and builder.
 pub mod arp;
+
+/// LLDP packet parser and builder.
+pub mod lldp;
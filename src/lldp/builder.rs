@@ -0,0 +1,313 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::net::IpAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::lldp::Packet;
+use crate::lldp::tlv::{self, Kind, Capabilities, ChassisSubtype, PortSubtype, MacPhy};
+
+/// LLDP Data Unit builder.
+///
+/// The Chassis ID, Port ID and Time To Live TLVs have to be added first, in
+/// this order, the End of LLDPDU TLV is added when building.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	mandatory: usize,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(0)?;
+
+		Ok(Builder {
+			buffer:    buffer,
+			finalizer: Default::default(),
+
+			mandatory: 0,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if self.mandatory < 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		// End of LLDPDU.
+		self.buffer.more(2)?;
+
+		self.finalizer.build(self.buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Append a TLV with the given type and value.
+	pub fn tlv(mut self, kind: Kind, value: &[u8]) -> Result<Self> {
+		if kind == Kind::End || value.len() > 511 {
+			Err(Error::InvalidValue)?
+		}
+
+		let position = match kind {
+			Kind::ChassisId  => Some(0),
+			Kind::PortId     => Some(1),
+			Kind::TimeToLive => Some(2),
+			_                => None,
+		};
+
+		match position {
+			Some(position) if position < self.mandatory =>
+				Err(Error::AlreadyDefined)?,
+
+			Some(position) if position > self.mandatory =>
+				Err(Error::InvalidPacket)?,
+
+			None if self.mandatory < 3 =>
+				Err(Error::InvalidPacket)?,
+
+			Some(_) =>
+				self.mandatory += 1,
+
+			None =>
+				(),
+		}
+
+		let offset = self.buffer.length();
+		self.buffer.more(2 + value.len())?;
+
+		let header = (u16::from(Into::<u8>::into(kind)) << 9) | value.len() as u16;
+		let data   = &mut self.buffer.data_mut()[offset ..];
+		data[.. 2].copy_from_slice(&header.to_be_bytes());
+		data[2 ..].copy_from_slice(value);
+
+		Ok(self)
+	}
+
+	/// Chassis ID.
+	pub fn chassis_id(self, subtype: ChassisSubtype, id: &[u8]) -> Result<Self> {
+		if id.is_empty() || id.len() > 255 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut value = vec![subtype.into()];
+		value.extend_from_slice(id);
+
+		self.tlv(Kind::ChassisId, &value)
+	}
+
+	/// Port ID.
+	pub fn port_id(self, subtype: PortSubtype, id: &[u8]) -> Result<Self> {
+		if id.is_empty() || id.len() > 255 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut value = vec![subtype.into()];
+		value.extend_from_slice(id);
+
+		self.tlv(Kind::PortId, &value)
+	}
+
+	/// Seconds the information is valid for.
+	pub fn ttl(self, value: u16) -> Result<Self> {
+		self.tlv(Kind::TimeToLive, &value.to_be_bytes())
+	}
+
+	/// Port description.
+	pub fn port_description(self, value: &str) -> Result<Self> {
+		self.tlv(Kind::PortDescription, value.as_bytes())
+	}
+
+	/// System name.
+	pub fn system_name(self, value: &str) -> Result<Self> {
+		self.tlv(Kind::SystemName, value.as_bytes())
+	}
+
+	/// System description.
+	pub fn system_description(self, value: &str) -> Result<Self> {
+		self.tlv(Kind::SystemDescription, value.as_bytes())
+	}
+
+	/// Supported and enabled system capabilities.
+	pub fn capabilities(self, system: Capabilities, enabled: Capabilities) -> Result<Self> {
+		let mut value = [0u8; 4];
+		value[.. 2].copy_from_slice(&system.bits().to_be_bytes());
+		value[2 ..].copy_from_slice(&enabled.bits().to_be_bytes());
+
+		self.tlv(Kind::SystemCapabilities, &value)
+	}
+
+	/// Management address reachable through the given interface index.
+	pub fn management_address(self, address: IpAddr, interface: u32) -> Result<Self> {
+		let (subtype, octets) = match address {
+			IpAddr::V4(address) => (1, address.octets().to_vec()),
+			IpAddr::V6(address) => (2, address.octets().to_vec()),
+		};
+
+		let mut value = vec![octets.len() as u8 + 1, subtype];
+		value.extend_from_slice(&octets);
+		value.push(2);
+		value.extend_from_slice(&interface.to_be_bytes());
+		value.push(0);
+
+		self.tlv(Kind::ManagementAddress, &value)
+	}
+
+	/// Organizationally specific TLV.
+	pub fn organization(self, oui: [u8; 3], subtype: u8, info: &[u8]) -> Result<Self> {
+		let mut value = oui.to_vec();
+		value.push(subtype);
+		value.extend_from_slice(info);
+
+		self.tlv(Kind::OrganizationallySpecific, &value)
+	}
+
+	/// IEEE 802.1 Port VLAN ID.
+	pub fn port_vlan_id(self, vid: u16) -> Result<Self> {
+		if vid > 0xfff {
+			Err(Error::InvalidValue)?
+		}
+
+		self.organization(tlv::IEEE_802_1, 1, &vid.to_be_bytes())
+	}
+
+	/// IEEE 802.1 VLAN Name.
+	pub fn vlan_name(self, vid: u16, name: &str) -> Result<Self> {
+		if vid > 0xfff || name.len() > 32 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut value = vid.to_be_bytes().to_vec();
+		value.push(name.len() as u8);
+		value.extend_from_slice(name.as_bytes());
+
+		self.organization(tlv::IEEE_802_1, 3, &value)
+	}
+
+	/// IEEE 802.3 MAC/PHY Configuration/Status.
+	pub fn mac_phy(self, value: MacPhy) -> Result<Self> {
+		let mut info = [0u8; 5];
+		info[0] = value.autonegotiation;
+		info[1 .. 3].copy_from_slice(&value.advertised.to_be_bytes());
+		info[3 ..].copy_from_slice(&value.mau.to_be_bytes());
+
+		self.organization(tlv::IEEE_802_3, 1, &info)
+	}
+
+	/// IEEE 802.3 Maximum Frame Size.
+	pub fn maximum_frame_size(self, value: u16) -> Result<Self> {
+		self.organization(tlv::IEEE_802_3, 4, &value.to_be_bytes())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::lldp;
+	use crate::lldp::tlv::{Kind, Capabilities, ChassisSubtype, PortSubtype, MacPhy};
+
+	#[test]
+	fn simple() {
+		let packet = ether::Builder::default()
+			.destination("01:80:c2:00:00:0e".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.lldp().unwrap()
+				.chassis_id(ChassisSubtype::MacAddress, &[0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3]).unwrap()
+				.port_id(PortSubtype::InterfaceName, b"eth0").unwrap()
+				.ttl(120).unwrap()
+				.system_name("router").unwrap()
+				.capabilities(Capabilities::ROUTER | Capabilities::BRIDGE, Capabilities::ROUTER).unwrap()
+				.management_address("192.168.0.1".parse().unwrap(), 2).unwrap()
+				.port_vlan_id(100).unwrap()
+				.vlan_name(100, "lan").unwrap()
+				.mac_phy(MacPhy { autonegotiation: 0x03, advertised: 0x6c00, mau: 30 }).unwrap()
+				.maximum_frame_size(1522).unwrap()
+				.build().unwrap();
+
+		let ether = ether::Packet::new(packet).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Lldp);
+
+		let lldp = lldp::Packet::new(ether.payload()).unwrap();
+		assert_eq!(lldp.ttl().unwrap(), 120);
+		assert_eq!(lldp.payload().len(), ether.payload().len());
+
+		let tlvs = lldp.tlvs().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(tlvs.len(), 10);
+
+		assert_eq!(tlvs[0].chassis_id().unwrap(), (ChassisSubtype::MacAddress, &[0xe4, 0xb3, 0x18, 0x26, 0x63, 0xa3][..]));
+		assert_eq!(tlvs[1].port_id().unwrap(), (PortSubtype::InterfaceName, &b"eth0"[..]));
+		assert_eq!(tlvs[3].system_name().unwrap(), "router");
+		assert_eq!(tlvs[4].capabilities().unwrap(), (Capabilities::ROUTER | Capabilities::BRIDGE, Capabilities::ROUTER));
+
+		let address = tlvs[5].management_address().unwrap();
+		assert_eq!(address.ip(), Some("192.168.0.1".parse().unwrap()));
+		assert_eq!(address.interface(), 2);
+		assert!(address.oid().is_empty());
+
+		assert_eq!(tlvs[6].organization().unwrap().port_vlan_id().unwrap(), 100);
+		assert_eq!(tlvs[7].organization().unwrap().vlan_name().unwrap(), (100, "lan"));
+		assert_eq!(tlvs[8].organization().unwrap().mac_phy().unwrap().mau, 30);
+		assert_eq!(tlvs[9].organization().unwrap().maximum_frame_size().unwrap(), 1522);
+		assert!(tlvs[9].organization().unwrap().port_vlan_id().is_err());
+
+		assert_eq!(lldp.find(Kind::SystemName).unwrap().system_name().unwrap(), "router");
+		assert!(lldp.find(Kind::PortDescription).is_none());
+	}
+
+	#[test]
+	fn order() {
+		assert!(lldp::Builder::default()
+			.port_id(PortSubtype::InterfaceName, b"eth0").is_err());
+
+		assert!(lldp::Builder::default()
+			.chassis_id(ChassisSubtype::LocallyAssigned, b"a").unwrap()
+			.system_name("router").is_err());
+
+		assert!(lldp::Builder::default()
+			.chassis_id(ChassisSubtype::LocallyAssigned, b"a").unwrap()
+			.chassis_id(ChassisSubtype::LocallyAssigned, b"b").is_err());
+
+		assert!(lldp::Builder::default()
+			.chassis_id(ChassisSubtype::LocallyAssigned, b"a").unwrap()
+			.port_id(PortSubtype::LocallyAssigned, b"b").unwrap()
+			.build().is_err());
+	}
+}
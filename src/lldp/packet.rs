@@ -0,0 +1,196 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::lldp::tlv::{self, Tlv, TlvIter};
+
+/// LLDP Data Unit parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  0,
+		max:  0,
+		size: 0,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.end(),
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("lldp::Packet")
+			.field("tlvs", &self.tlvs().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an LLDPDU without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an LLDPDU, checking the buffer contents are correct.
+	///
+	/// # Note
+	///
+	/// The Chassis ID, Port ID and Time To Live TLVs are mandatory and have to
+	/// be the first ones, in this order.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		let packet = Packet::unchecked(buffer);
+
+		{
+			let mut tlvs = packet.tlvs();
+
+			tlvs.next().ok_or(Error::InvalidPacket)??.chassis_id()?;
+			tlvs.next().ok_or(Error::InvalidPacket)??.port_id()?;
+			tlvs.next().ok_or(Error::InvalidPacket)??.ttl()?;
+
+			for tlv in tlvs {
+				tlv?;
+			}
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		let end = self.end();
+		self.buffer.as_ref()[.. end].split_at(0)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		let end = self.end();
+		self.buffer.as_mut()[.. end].split_at_mut(0)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// End of the LLDPDU, including the End of LLDPDU TLV but excluding any
+	/// padding after it.
+	fn end(&self) -> usize {
+		use crate::size::Size;
+
+		let buffer = self.buffer.as_ref();
+		let end    = self.tlvs().map_while(|tlv| tlv.ok()).map(|tlv| tlv.size()).sum::<usize>();
+
+		// Account for the End of LLDPDU TLV.
+		match end {
+			end if buffer.len() >= end + 2 && buffer[end .. end + 2] == [0, 0] =>
+				end + 2,
+
+			end =>
+				end
+		}
+	}
+
+	/// Iterator over the TLVs.
+	pub fn tlvs(&self) -> TlvIter<'_> {
+		TlvIter {
+			buffer: self.buffer.as_ref(),
+		}
+	}
+
+	/// Find the first TLV of the given type.
+	pub fn find(&self, kind: tlv::Kind) -> Option<Tlv<&[u8]>> {
+		self.tlvs().filter_map(|tlv| tlv.ok()).find(|tlv| tlv.kind() == kind)
+	}
+
+	/// Seconds the information is valid for, zero means it has to be removed.
+	pub fn ttl(&self) -> Result<u16> {
+		self.find(tlv::Kind::TimeToLive).ok_or(Error::InvalidPacket)?.ttl()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::lldp;
+	use crate::lldp::tlv::{Kind, ChassisSubtype, PortSubtype};
+
+	#[test]
+	fn values() {
+		let raw = [0x02u8, 0x07, 0x04, 0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e, 0x04, 0x05, 0x05, 0x65, 0x74, 0x68, 0x30, 0x06, 0x02, 0x00, 0x78, 0x0a, 0x02, 0x73, 0x77, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+		let lldp = lldp::Packet::new(&raw[..]).unwrap();
+		assert_eq!(lldp.payload().len(), 26);
+		assert_eq!(lldp.ttl().unwrap(), 120);
+
+		let tlvs = lldp.tlvs().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(tlvs.len(), 4);
+		assert_eq!(tlvs[0].chassis_id().unwrap(), (ChassisSubtype::MacAddress, &[0x00, 0x1b, 0x21, 0x3c, 0x4d, 0x5e][..]));
+		assert_eq!(tlvs[1].port_id().unwrap(), (PortSubtype::InterfaceName, &b"eth0"[..]));
+		assert_eq!(tlvs[3].kind(), Kind::SystemName);
+		assert_eq!(tlvs[3].system_name().unwrap(), "sw");
+		assert!(tlvs[3].port_description().is_err());
+
+		// Port ID before Chassis ID.
+		assert!(lldp::Packet::new(&raw[9 ..]).is_err());
+
+		// Truncated System Name.
+		assert!(lldp::Packet::new(&raw[.. 23]).is_err());
+	}
+}
@@ -0,0 +1,674 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::fmt;
+use std::str;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use byteorder::{ReadBytesExt, BigEndian};
+use bitflags::bitflags;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM};
+
+/// OUI for IEEE 802.1 organizationally specific TLVs.
+pub const IEEE_802_1: [u8; 3] = [0x00, 0x80, 0xc2];
+
+/// OUI for IEEE 802.3 organizationally specific TLVs.
+pub const IEEE_802_3: [u8; 3] = [0x00, 0x12, 0x0f];
+
+/// LLDP TLV parser.
+pub struct Tlv<B> {
+	buffer: B,
+}
+
+sized!(Tlv,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  0,
+		max:  511,
+		size: p => p.length() as usize,
+	});
+
+/// TLV types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+	///
+	End,
+
+	///
+	ChassisId,
+
+	///
+	PortId,
+
+	///
+	TimeToLive,
+
+	///
+	PortDescription,
+
+	///
+	SystemName,
+
+	///
+	SystemDescription,
+
+	///
+	SystemCapabilities,
+
+	///
+	ManagementAddress,
+
+	///
+	OrganizationallySpecific,
+
+	///
+	Unknown(u8),
+}
+
+impl From<u8> for Kind {
+	fn from(value: u8) -> Kind {
+		use self::Kind::*;
+
+		match value {
+			0   => End,
+			1   => ChassisId,
+			2   => PortId,
+			3   => TimeToLive,
+			4   => PortDescription,
+			5   => SystemName,
+			6   => SystemDescription,
+			7   => SystemCapabilities,
+			8   => ManagementAddress,
+			127 => OrganizationallySpecific,
+			n   => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for Kind {
+	fn into(self) -> u8 {
+		use self::Kind::*;
+
+		match self {
+			End                      => 0,
+			ChassisId                => 1,
+			PortId                   => 2,
+			TimeToLive               => 3,
+			PortDescription          => 4,
+			SystemName               => 5,
+			SystemDescription        => 6,
+			SystemCapabilities       => 7,
+			ManagementAddress        => 8,
+			OrganizationallySpecific => 127,
+			Unknown(n)               => n,
+		}
+	}
+}
+
+/// Chassis ID subtypes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ChassisSubtype {
+	///
+	ChassisComponent,
+
+	///
+	InterfaceAlias,
+
+	///
+	PortComponent,
+
+	///
+	MacAddress,
+
+	///
+	NetworkAddress,
+
+	///
+	InterfaceName,
+
+	///
+	LocallyAssigned,
+
+	///
+	Unknown(u8),
+}
+
+impl From<u8> for ChassisSubtype {
+	fn from(value: u8) -> ChassisSubtype {
+		use self::ChassisSubtype::*;
+
+		match value {
+			1 => ChassisComponent,
+			2 => InterfaceAlias,
+			3 => PortComponent,
+			4 => MacAddress,
+			5 => NetworkAddress,
+			6 => InterfaceName,
+			7 => LocallyAssigned,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for ChassisSubtype {
+	fn into(self) -> u8 {
+		use self::ChassisSubtype::*;
+
+		match self {
+			ChassisComponent => 1,
+			InterfaceAlias   => 2,
+			PortComponent    => 3,
+			MacAddress       => 4,
+			NetworkAddress   => 5,
+			InterfaceName    => 6,
+			LocallyAssigned  => 7,
+			Unknown(n)       => n,
+		}
+	}
+}
+
+/// Port ID subtypes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum PortSubtype {
+	///
+	InterfaceAlias,
+
+	///
+	PortComponent,
+
+	///
+	MacAddress,
+
+	///
+	NetworkAddress,
+
+	///
+	InterfaceName,
+
+	///
+	AgentCircuitId,
+
+	///
+	LocallyAssigned,
+
+	///
+	Unknown(u8),
+}
+
+impl From<u8> for PortSubtype {
+	fn from(value: u8) -> PortSubtype {
+		use self::PortSubtype::*;
+
+		match value {
+			1 => InterfaceAlias,
+			2 => PortComponent,
+			3 => MacAddress,
+			4 => NetworkAddress,
+			5 => InterfaceName,
+			6 => AgentCircuitId,
+			7 => LocallyAssigned,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for PortSubtype {
+	fn into(self) -> u8 {
+		use self::PortSubtype::*;
+
+		match self {
+			InterfaceAlias  => 1,
+			PortComponent   => 2,
+			MacAddress      => 3,
+			NetworkAddress  => 4,
+			InterfaceName   => 5,
+			AgentCircuitId  => 6,
+			LocallyAssigned => 7,
+			Unknown(n)      => n,
+		}
+	}
+}
+
+bitflags! {
+	/// System capabilities.
+	pub struct Capabilities: u16 {
+		///
+		const OTHER              = 0b000_0000_0001;
+
+		///
+		const REPEATER           = 0b000_0000_0010;
+
+		///
+		const BRIDGE             = 0b000_0000_0100;
+
+		///
+		const WLAN_ACCESS_POINT  = 0b000_0000_1000;
+
+		///
+		const ROUTER             = 0b000_0001_0000;
+
+		///
+		const TELEPHONE          = 0b000_0010_0000;
+
+		///
+		const DOCSIS_CABLE       = 0b000_0100_0000;
+
+		///
+		const STATION_ONLY       = 0b000_1000_0000;
+
+		///
+		const C_VLAN             = 0b001_0000_0000;
+
+		///
+		const S_VLAN             = 0b010_0000_0000;
+
+		///
+		const TWO_PORT_MAC_RELAY = 0b100_0000_0000;
+	}
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Tlv<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("lldp::Tlv")
+			.field("kind", &self.kind())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Tlv<B> {
+	/// Create a TLV without checking the buffer.
+	pub fn unchecked(buffer: B) -> Tlv<B> {
+		Tlv { buffer }
+	}
+
+	/// Parse a TLV, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Tlv<B>> {
+		use crate::size::header::Min;
+		use crate::size::Size;
+
+		let tlv = Tlv::unchecked(buffer);
+
+		if tlv.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if tlv.buffer.as_ref().len() < tlv.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(tlv)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Tlv<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Tlv<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Tlv<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(2)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Tlv<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(2)
+	}
+}
+
+impl<B: AsRef<[u8]>> Tlv<B> {
+	/// TLV type.
+	pub fn kind(&self) -> Kind {
+		(self.buffer.as_ref()[0] >> 1).into()
+	}
+
+	/// Length of the value.
+	pub fn length(&self) -> u16 {
+		(&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap() & 0x1ff
+	}
+}
+
+impl<B: AsRef<[u8]>> Tlv<B> {
+	/// The value of the TLV, if it's of the given type and at least the given
+	/// length.
+	fn expect(&self, kind: Kind, length: usize) -> Result<&[u8]> {
+		if self.kind() != kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.payload().len() < length {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload())
+	}
+
+	/// The value of the TLV as a string.
+	fn string(&self, kind: Kind) -> Result<&str> {
+		str::from_utf8(self.expect(kind, 0)?).map_err(|_| Error::InvalidPacket)
+	}
+
+	/// Subtype and identifier of a Chassis ID TLV.
+	pub fn chassis_id(&self) -> Result<(ChassisSubtype, &[u8])> {
+		let value = self.expect(Kind::ChassisId, 2)?;
+		Ok((value[0].into(), &value[1 ..]))
+	}
+
+	/// Subtype and identifier of a Port ID TLV.
+	pub fn port_id(&self) -> Result<(PortSubtype, &[u8])> {
+		let value = self.expect(Kind::PortId, 2)?;
+		Ok((value[0].into(), &value[1 ..]))
+	}
+
+	/// Seconds of a Time To Live TLV.
+	pub fn ttl(&self) -> Result<u16> {
+		Ok(self.expect(Kind::TimeToLive, 2)?.read_u16::<BigEndian>()?)
+	}
+
+	/// Value of a Port Description TLV.
+	pub fn port_description(&self) -> Result<&str> {
+		self.string(Kind::PortDescription)
+	}
+
+	/// Value of a System Name TLV.
+	pub fn system_name(&self) -> Result<&str> {
+		self.string(Kind::SystemName)
+	}
+
+	/// Value of a System Description TLV.
+	pub fn system_description(&self) -> Result<&str> {
+		self.string(Kind::SystemDescription)
+	}
+
+	/// Supported and enabled capabilities of a System Capabilities TLV.
+	pub fn capabilities(&self) -> Result<(Capabilities, Capabilities)> {
+		let mut value = self.expect(Kind::SystemCapabilities, 4)?;
+
+		Ok((Capabilities::from_bits_truncate(value.read_u16::<BigEndian>()?),
+		    Capabilities::from_bits_truncate(value.read_u16::<BigEndian>()?)))
+	}
+
+	/// Parse a Management Address TLV.
+	pub fn management_address(&self) -> Result<ManagementAddress<&[u8]>> {
+		if self.kind() != Kind::ManagementAddress {
+			Err(Error::InvalidPacket)?
+		}
+
+		ManagementAddress::new(self.payload())
+	}
+
+	/// Parse an organizationally specific TLV.
+	pub fn organization(&self) -> Result<Organization<&[u8]>> {
+		if self.kind() != Kind::OrganizationallySpecific {
+			Err(Error::InvalidPacket)?
+		}
+
+		Organization::new(self.payload())
+	}
+}
+
+/// Management Address TLV parser.
+pub struct ManagementAddress<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for ManagementAddress<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("lldp::tlv::ManagementAddress")
+			.field("subtype", &self.subtype())
+			.field("address", &self.address())
+			.field("interface_subtype", &self.interface_subtype())
+			.field("interface", &self.interface())
+			.field("oid", &self.oid())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> ManagementAddress<B> {
+	/// Parse a Management Address TLV value.
+	pub fn new(buffer: B) -> Result<ManagementAddress<B>> {
+		let value = buffer.as_ref();
+
+		// The address string length includes the subtype.
+		if value.is_empty() || value[0] < 2 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let length = value[0] as usize + 1;
+
+		if value.len() < length + 6 || value.len() < length + 6 + value[length + 5] as usize {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(ManagementAddress { buffer })
+	}
+
+	fn offset(&self) -> usize {
+		self.buffer.as_ref()[0] as usize + 1
+	}
+
+	/// Address family number, as assigned by IANA.
+	pub fn subtype(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Raw address.
+	pub fn address(&self) -> &[u8] {
+		&self.buffer.as_ref()[2 .. self.offset()]
+	}
+
+	/// Address as an IP address, if it is one.
+	pub fn ip(&self) -> Option<IpAddr> {
+		let address = self.address();
+
+		match (self.subtype(), address.len()) {
+			(1, 4) =>
+				Some(Ipv4Addr::new(address[0], address[1], address[2], address[3]).into()),
+
+			(2, 16) => {
+				let mut octets = [0u8; 16];
+				octets.copy_from_slice(address);
+
+				Some(Ipv6Addr::from(octets).into())
+			}
+
+			_ =>
+				None
+		}
+	}
+
+	/// Interface numbering subtype.
+	pub fn interface_subtype(&self) -> u8 {
+		self.buffer.as_ref()[self.offset()]
+	}
+
+	/// Interface number.
+	pub fn interface(&self) -> u32 {
+		(&self.buffer.as_ref()[self.offset() + 1 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Object identifier.
+	pub fn oid(&self) -> &[u8] {
+		let offset = self.offset() + 5;
+		let length = self.buffer.as_ref()[offset] as usize;
+
+		&self.buffer.as_ref()[offset + 1 .. offset + 1 + length]
+	}
+}
+
+/// Organizationally specific TLV parser.
+pub struct Organization<B> {
+	buffer: B,
+}
+
+/// IEEE 802.3 MAC/PHY Configuration/Status.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct MacPhy {
+	/// Auto-negotiation support and status.
+	pub autonegotiation: u8,
+
+	/// Auto-negotiation advertised capability.
+	pub advertised: u16,
+
+	/// Operational MAU type.
+	pub mau: u16,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Organization<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("lldp::tlv::Organization")
+			.field("oui", &self.oui())
+			.field("subtype", &self.subtype())
+			.field("value", &self.value())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Organization<B> {
+	/// Parse an organizationally specific TLV value.
+	pub fn new(buffer: B) -> Result<Organization<B>> {
+		if buffer.as_ref().len() < 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Organization { buffer })
+	}
+
+	/// Organizationally Unique Identifier.
+	pub fn oui(&self) -> [u8; 3] {
+		let mut oui = [0u8; 3];
+		oui.copy_from_slice(&self.buffer.as_ref()[0 .. 3]);
+
+		oui
+	}
+
+	/// Subtype, defined by the organization.
+	pub fn subtype(&self) -> u8 {
+		self.buffer.as_ref()[3]
+	}
+
+	/// Value, defined by the organization.
+	pub fn value(&self) -> &[u8] {
+		&self.buffer.as_ref()[4 ..]
+	}
+
+	/// The value, if it's of the given organization and subtype and at least
+	/// the given length.
+	fn expect(&self, oui: [u8; 3], subtype: u8, length: usize) -> Result<&[u8]> {
+		if self.oui() != oui || self.subtype() != subtype {
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.value().len() < length {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.value())
+	}
+
+	/// VLAN identifier of an IEEE 802.1 Port VLAN ID TLV.
+	pub fn port_vlan_id(&self) -> Result<u16> {
+		Ok(self.expect(IEEE_802_1, 1, 2)?.read_u16::<BigEndian>()?)
+	}
+
+	/// VLAN identifier and name of an IEEE 802.1 VLAN Name TLV.
+	pub fn vlan_name(&self) -> Result<(u16, &str)> {
+		let value  = self.expect(IEEE_802_1, 3, 3)?;
+		let vid    = (&value[0 ..]).read_u16::<BigEndian>()?;
+		let length = value[2] as usize;
+
+		if value.len() < 3 + length {
+			Err(Error::InvalidPacket)?
+		}
+
+		let name = str::from_utf8(&value[3 .. 3 + length]).map_err(|_| Error::InvalidPacket)?;
+
+		Ok((vid, name))
+	}
+
+	/// Value of an IEEE 802.3 MAC/PHY Configuration/Status TLV.
+	pub fn mac_phy(&self) -> Result<MacPhy> {
+		let mut value = self.expect(IEEE_802_3, 1, 5)?;
+
+		Ok(MacPhy {
+			autonegotiation: value.read_u8()?,
+			advertised:      value.read_u16::<BigEndian>()?,
+			mau:             value.read_u16::<BigEndian>()?,
+		})
+	}
+
+	/// Value of an IEEE 802.3 Maximum Frame Size TLV.
+	pub fn maximum_frame_size(&self) -> Result<u16> {
+		Ok(self.expect(IEEE_802_3, 4, 2)?.read_u16::<BigEndian>()?)
+	}
+}
+
+/// Iterator over LLDP TLVs, stopping at the End of LLDPDU TLV.
+pub struct TlvIter<'a> {
+	pub(in crate::lldp) buffer: &'a [u8],
+}
+
+impl<'a> Iterator for TlvIter<'a> {
+	type Item = Result<Tlv<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use crate::size::Size;
+
+		if self.buffer.is_empty() {
+			return None;
+		}
+
+		match Tlv::new(self.buffer) {
+			Ok(tlv) => {
+				if tlv.kind() == Kind::End {
+					self.buffer = &[];
+					return None;
+				}
+
+				self.buffer = &self.buffer[tlv.size() ..];
+				Some(Ok(tlv))
+			}
+
+			Err(error) => {
+				self.buffer = &[];
+				Some(Err(error))
+			}
+		}
+	}
+}
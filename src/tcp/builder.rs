@@ -21,6 +21,7 @@ use crate::error::*;
 use crate::ip;
 use crate::packet::{AsPacket, AsPacketMut};
 use crate::tcp::checksum;
+use crate::tcp::option;
 use crate::tcp::Flags;
 use crate::tcp::Packet;
 
@@ -65,11 +66,9 @@ impl<B: Buffer> Build<B> for Builder<B> {
     }
 
     fn build(mut self) -> Result<B::Inner> {
-        self.prepare();
+        self.prepare()?;
 
-        let mut buffer = self.buffer.into_inner();
-        self.finalizer.finalize(buffer.as_mut())?;
-        Ok(buffer)
+        self.finalizer.build(self.buffer)
     }
 }
 
@@ -134,12 +133,78 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
+    /// Add an option, No Operation padding is inserted to satisfy the
+    /// alignment of known options.
+    pub fn option(mut self, number: option::Number, value: &[u8]) -> Result<Self> {
+        if number == option::Number::End || number == option::Number::NoOperation {
+            Err(Error::InvalidValue)?
+        }
+
+        let (multiple, remainder) = alignment(number);
+        let position = self.buffer.length();
+        let padding = (multiple + remainder - position % multiple) % multiple;
+
+        let mut data = vec![option::Number::NoOperation.into(); padding];
+        data.push(number.into());
+        data.push((2 + value.len()) as u8);
+        data.extend_from_slice(value);
+
+        self.extend(&data)?;
+        Ok(self)
+    }
+
+    /// Add a Maximum Segment Size option.
+    pub fn mss(self, value: u16) -> Result<Self> {
+        self.option(option::Number::MaximumSegmentSize, &value.to_be_bytes())
+    }
+
+    /// Add a Window Scale option with the given shift count.
+    pub fn window_scale(self, value: u8) -> Result<Self> {
+        // The shift count is capped at 14 by RFC 7323.
+        if value > 14 {
+            Err(Error::InvalidValue)?
+        }
+
+        self.option(option::Number::WindowScale, &[value])
+    }
+
+    /// Add a Selective Acknowledgment Permitted option.
+    pub fn sack_permitted(self) -> Result<Self> {
+        self.option(option::Number::SelectiveAcknowledgmentPermitted, &[])
+    }
+
+    /// Add a Selective Acknowledgment option with the given left and right
+    /// edges.
+    pub fn sack(self, blocks: &[(u32, u32)]) -> Result<Self> {
+        if blocks.is_empty() || blocks.len() > 4 {
+            Err(Error::InvalidValue)?
+        }
+
+        let mut data = Vec::with_capacity(blocks.len() * 8);
+        for &(left, right) in blocks {
+            data.extend_from_slice(&left.to_be_bytes());
+            data.extend_from_slice(&right.to_be_bytes());
+        }
+
+        self.option(option::Number::SelectiveAcknowledgment, &data)
+    }
+
+    /// Add a Timestamp option with the timestamp value and echo reply.
+    pub fn timestamp(self, value: u32, echo: u32) -> Result<Self> {
+        let mut data = [0u8; 8];
+        data[..4].copy_from_slice(&value.to_be_bytes());
+        data[4..].copy_from_slice(&echo.to_be_bytes());
+
+        self.option(option::Number::Timestamp, &data)
+    }
+
     /// Payload for the packet.
     pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
         if self.payload {
             Err(Error::AlreadyDefined)?
         }
 
+        self.close()?;
         self.payload = true;
 
         for byte in value {
@@ -151,7 +216,38 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
-    fn prepare(&mut self) {
+    fn extend(&mut self, value: &[u8]) -> Result<()> {
+        use crate::size::header::Max;
+
+        if self.payload {
+            Err(Error::AlreadyDefined)?
+        }
+
+        if self.buffer.length() + value.len() > Packet::<()>::max() {
+            Err(Error::InvalidPacket)?
+        }
+
+        let offset = self.buffer.length();
+        self.buffer.more(value.len())?;
+        self.buffer.data_mut()[offset..].copy_from_slice(value);
+        self.options = true;
+
+        Ok(())
+    }
+
+    /// Pad the options to a multiple of 4 octets.
+    fn close(&mut self) -> Result<()> {
+        if self.payload || !self.options {
+            return Ok(());
+        }
+
+        let padding = (4 - self.buffer.length() % 4) % 4;
+        self.extend(&[option::Number::End.into(); 3][..padding])
+    }
+
+    fn prepare(&mut self) -> Result<()> {
+        self.close()?;
+
         let ip = self.ip;
         let length = self.buffer.length();
         let payload_length = self.payload_length;
@@ -174,6 +270,18 @@ impl<B: Buffer> Builder<B> {
 
             Ok(())
         });
+
+        Ok(())
+    }
+}
+
+/// Alignment of known options as (multiple, remainder), so their 32-bit
+/// fields end up aligned.
+fn alignment(number: option::Number) -> (usize, usize) {
+    match number {
+        option::Number::SelectiveAcknowledgment | option::Number::Timestamp => (4, 2),
+
+        _ => (1, 0),
     }
 }
 
@@ -228,4 +336,89 @@ mod test {
         assert_eq!(tcp.flags(), tcp::flag::SYN);
         assert!(tcp.is_valid(&ip::Packet::from(&ip)));
     }
+
+    #[test]
+    fn options() {
+        let packet = ip::v4::Builder::default()
+            .source("192.168.0.79".parse().unwrap())
+            .unwrap()
+            .destination("10.0.0.1".parse().unwrap())
+            .unwrap()
+            .tcp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(80)
+            .unwrap()
+            .flags(tcp::flag::SYN)
+            .unwrap()
+            .mss(1460)
+            .unwrap()
+            .sack_permitted()
+            .unwrap()
+            .timestamp(0xdeadbeef, 0)
+            .unwrap()
+            .window_scale(7)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+        assert_eq!(tcp.offset(), 10);
+        assert_eq!(tcp.payload(), b"lol");
+
+        let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options.len(), 4);
+        assert_eq!(options[0].mss().unwrap(), 1460);
+        assert_eq!(
+            options[1].number(),
+            tcp::option::Number::SelectiveAcknowledgmentPermitted
+        );
+        assert_eq!(options[2].timestamp().unwrap(), (0xdeadbeef, 0));
+        assert_eq!(options[3].window_scale().unwrap(), 7);
+    }
+
+    #[test]
+    fn sack() {
+        let packet = ip::v4::Builder::default()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::ACK)
+            .unwrap()
+            .sack(&[(1, 2), (3, 4)])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.offset(), 10);
+        assert_eq!(&ip.payload()[20..24], &[0x01, 0x01, 0x05, 0x12]);
+
+        let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options.len(), 3);
+        assert_eq!(
+            options[2].sack().unwrap().collect::<Vec<_>>(),
+            vec![(1, 2), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn options_too_long() {
+        assert!(tcp::Builder::default()
+            .sack(&[(1, 2), (3, 4), (5, 6), (7, 8)])
+            .unwrap()
+            .timestamp(0, 0)
+            .is_err());
+
+        assert!(tcp::Builder::default()
+            .payload(b"lol")
+            .unwrap()
+            .mss(1460)
+            .is_err());
+    }
 }
@@ -0,0 +1,769 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::collections::{HashMap, BTreeSet};
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip;
+use crate::tcp::Packet;
+use crate::tcp::Flags;
+use crate::tcp::Direction;
+use crate::tcp::option;
+
+/// State of a tracked connection.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum State {
+	/// Nothing has been seen yet.
+	None,
+
+	/// The SYN has been seen.
+	SynSent,
+
+	/// The SYN-ACK has been seen.
+	SynReceived,
+
+	/// The handshake completed.
+	Established,
+
+	/// One side sent a FIN.
+	FinWait,
+
+	/// The FIN of one side has been acknowledged.
+	CloseWait,
+
+	/// Both sides sent a FIN.
+	LastAck,
+
+	/// Both FINs have been acknowledged.
+	TimeWait,
+
+	/// The connection was reset.
+	Closed,
+}
+
+/// What's known about one side of a connection.
+#[derive(Default, Copy, Clone, Debug)]
+struct Side {
+	/// Sequence number following the highest one sent.
+	end: u32,
+
+	/// Highest sequence number the peer allows to send.
+	max_end: u32,
+
+	/// Largest window advertised, scaled.
+	max_window: u32,
+
+	/// Window scale shift count.
+	scale: Option<u8>,
+
+	/// Sequence number following the FIN, if one was sent.
+	fin: Option<u32>,
+
+	/// Whether the FIN was acknowledged.
+	fin_acked: bool,
+}
+
+/// TCP connection state machine.
+///
+/// It follows the handshake, the FIN and RST transitions, and checks every
+/// segment falls within the sequence and acknowledgment windows of the
+/// connection, taking window scaling into account.
+#[derive(Copy, Clone, Debug)]
+pub struct Connection {
+	state:    State,
+	original: Side,
+	reply:    Side,
+}
+
+impl Default for Connection {
+	fn default() -> Self {
+		Connection {
+			state:    State::None,
+			original: Default::default(),
+			reply:    Default::default(),
+		}
+	}
+}
+
+/// Check if the first sequence number comes before the second, taking
+/// wraparound into account.
+fn before(a: u32, b: u32) -> bool {
+	(a.wrapping_sub(b) as i32) < 0
+}
+
+/// Check if the first sequence number comes after the second, taking
+/// wraparound into account.
+fn after(a: u32, b: u32) -> bool {
+	before(b, a)
+}
+
+/// Window Scale option of a segment, if any.
+fn window_scale<B: AsRef<[u8]>>(packet: &Packet<B>) -> Result<Option<u8>> {
+	for option in packet.options() {
+		let option = option?;
+
+		if option.number() == option::Number::WindowScale {
+			// The shift count is capped at 14 by RFC 7323.
+			return Ok(Some(option.window_scale()?.min(14)));
+		}
+	}
+
+	Ok(None)
+}
+
+impl Connection {
+	/// Create an untracked connection.
+	pub fn new() -> Connection {
+		Default::default()
+	}
+
+	/// Current state.
+	pub fn state(&self) -> State {
+		self.state
+	}
+
+	/// Update the state with a segment sent in the given direction.
+	///
+	/// # Errors
+	///
+	/// A segment that's not valid for the current state, or that falls
+	/// outside the windows, returns `Error::InvalidPacket` and leaves the
+	/// state untouched.
+	pub fn update<B: AsRef<[u8]>>(&mut self, direction: Direction, packet: &Packet<B>) -> Result<State> {
+		// An unchecked packet could have a header too short for options.
+		if packet.offset() < 5 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let flags = packet.flags();
+		let syn   = flags.contains(Flags::SYN);
+		let ack   = flags.contains(Flags::ACK);
+		let fin   = flags.contains(Flags::FIN);
+		let rst   = flags.contains(Flags::RST);
+
+		let sequence       = packet.sequence();
+		let acknowledgment = packet.acknowledgment();
+		let end            = sequence
+			.wrapping_add(packet.payload().len() as u32)
+			.wrapping_add(syn as u32)
+			.wrapping_add(fin as u32);
+
+		if syn && rst {
+			Err(Error::InvalidPacket)?
+		}
+
+		if syn {
+			let scale = window_scale(packet)?;
+
+			// The window in a SYN is never scaled.
+			let side = Side {
+				end:        end,
+				max_end:    0,
+				max_window: u32::from(packet.window()).max(1),
+				scale:      scale,
+				fin:        None,
+				fin_acked:  false,
+			};
+
+			match (direction, ack, self.state) {
+				(Direction::Original, false, State::None) |
+				(Direction::Original, false, State::TimeWait) |
+				(Direction::Original, false, State::Closed) => {
+					*self = Connection {
+						state:    State::SynSent,
+						original: side,
+						reply:    Default::default(),
+					};
+				}
+
+				// A retransmitted SYN.
+				(Direction::Original, false, State::SynSent) if end == self.original.end =>
+					(),
+
+				(Direction::Reply, true, State::SynSent) |
+				(Direction::Reply, true, State::SynReceived) => {
+					if acknowledgment != self.original.end {
+						Err(Error::InvalidPacket)?
+					}
+
+					self.reply = side;
+
+					// Scaling is only used if both sides agree on it.
+					if self.original.scale.is_none() || self.reply.scale.is_none() {
+						self.original.scale = None;
+						self.reply.scale    = None;
+					}
+
+					self.original.max_end = acknowledgment.wrapping_add(self.reply.max_window);
+					self.reply.max_end    = self.reply.end.wrapping_add(self.original.max_window);
+					self.state            = State::SynReceived;
+				}
+
+				_ =>
+					Err(Error::InvalidPacket)?
+			}
+
+			return Ok(self.state);
+		}
+
+		match self.state {
+			State::None | State::Closed =>
+				Err(Error::InvalidPacket)?,
+
+			// Only a refused connection is valid before the SYN-ACK.
+			State::SynSent => {
+				if direction == Direction::Reply && rst && ack && acknowledgment == self.original.end {
+					self.state = State::Closed;
+					return Ok(self.state);
+				}
+
+				Err(Error::InvalidPacket)?
+			}
+
+			_ =>
+				(),
+		}
+
+		let (sender, receiver) = match direction {
+			Direction::Original => (self.original, self.reply),
+			Direction::Reply    => (self.reply, self.original),
+		};
+
+		let window = u32::from(packet.window()) << sender.scale.unwrap_or(0);
+
+		// The data must fit in what the receiver allowed, and not be older
+		// than a window.
+		if after(sequence, sender.max_end) ||
+		   before(end, sender.end.wrapping_sub(receiver.max_window).wrapping_sub(1))
+		{
+			Err(Error::InvalidPacket)?
+		}
+
+		// Only data that was sent can be acknowledged.
+		if ack && (after(acknowledgment, receiver.end) ||
+		   before(acknowledgment, receiver.end.wrapping_sub(sender.max_window).wrapping_sub(1)))
+		{
+			Err(Error::InvalidPacket)?
+		}
+
+		if rst {
+			self.state = State::Closed;
+			return Ok(self.state);
+		}
+
+		let (mut sender, mut receiver) = (sender, receiver);
+
+		if after(end, sender.end) {
+			sender.end = end;
+		}
+
+		if window > sender.max_window {
+			sender.max_window = window;
+		}
+
+		if fin {
+			if sender.fin.is_some_and(|fin| fin != end) {
+				Err(Error::InvalidPacket)?
+			}
+
+			sender.fin = Some(end);
+		}
+
+		if ack {
+			let max_end = acknowledgment.wrapping_add(window);
+
+			if after(max_end, receiver.max_end) {
+				receiver.max_end = max_end;
+			}
+
+			if receiver.fin.is_some_and(|fin| !before(acknowledgment, fin)) {
+				receiver.fin_acked = true;
+			}
+		}
+
+		match direction {
+			Direction::Original => {
+				self.original = sender;
+				self.reply    = receiver;
+			}
+
+			Direction::Reply => {
+				self.reply    = sender;
+				self.original = receiver;
+			}
+		}
+
+		// The handshake completes with the acknowledgment of the SYN-ACK.
+		if self.state == State::SynReceived && direction == Direction::Original && ack {
+			self.state = State::Established;
+		}
+
+		if self.state != State::SynReceived {
+			let sides = [self.original, self.reply];
+			let fins  = sides.iter().filter(|side| side.fin.is_some()).count();
+			let acked = sides.iter().filter(|side| side.fin_acked).count();
+
+			self.state = match (fins, acked) {
+				(0, _) => State::Established,
+				(1, 0) => State::FinWait,
+				(1, _) => State::CloseWait,
+				(_, 2) => State::TimeWait,
+				_      => State::LastAck,
+			};
+		}
+
+		Ok(self.state)
+	}
+}
+
+/// Identifies a connection by its endpoints, as seen in the first segment.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+pub struct Key {
+	/// Address and port the segment comes from.
+	pub source: SocketAddr,
+
+	/// Address and port the segment goes to.
+	pub destination: SocketAddr,
+}
+
+impl Key {
+	/// Get the key of the given segment.
+	pub fn new<I: AsRef<[u8]>, B: AsRef<[u8]>>(ip: &ip::Packet<I>, packet: &Packet<B>) -> Key {
+		let (source, destination) = match *ip {
+			ip::Packet::V4(ref ip) =>
+				(IpAddr::V4(ip.source()), IpAddr::V4(ip.destination())),
+
+			ip::Packet::V6(ref ip) =>
+				(IpAddr::V6(ip.source()), IpAddr::V6(ip.final_destination())),
+		};
+
+		Key {
+			source:      SocketAddr::new(source, packet.source()),
+			destination: SocketAddr::new(destination, packet.destination()),
+		}
+	}
+
+	/// The key of the segments going the other way.
+	pub fn reverse(&self) -> Key {
+		Key {
+			source:      self.destination,
+			destination: self.source,
+		}
+	}
+}
+
+/// Resource limits for the tracker.
+#[derive(Copy, Clone, Debug)]
+pub struct Limits {
+	/// How long a connection is kept without seeing any segment during the
+	/// handshake.
+	pub handshake: Duration,
+
+	/// How long an established connection is kept without seeing any segment.
+	pub established: Duration,
+
+	/// How long a connection is kept without seeing any segment once one side
+	/// sent a FIN.
+	pub closing: Duration,
+
+	/// How long a connection is kept once both FINs have been acknowledged.
+	pub time_wait: Duration,
+
+	/// Maximum number of tracked connections.
+	pub connections: usize,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Limits {
+			handshake:   Duration::from_secs(60),
+			// The idle timeout RFC 5382 requires for established connections.
+			established: Duration::from_secs(2 * 60 * 60 + 4 * 60),
+			closing:     Duration::from_secs(2 * 60),
+			// Twice the Maximum Segment Lifetime of RFC 9293.
+			time_wait:   Duration::from_secs(4 * 60),
+			connections: 64 * 1024,
+		}
+	}
+}
+
+impl Limits {
+	/// How long a connection in the given state is kept without seeing any
+	/// segment.
+	pub fn timeout(&self, state: State) -> Duration {
+		match state {
+			State::Established =>
+				self.established,
+
+			State::FinWait | State::CloseWait | State::LastAck =>
+				self.closing,
+
+			State::TimeWait =>
+				self.time_wait,
+
+			State::None | State::SynSent | State::SynReceived | State::Closed =>
+				self.handshake,
+		}
+	}
+}
+
+/// Connection tracker, following many connections by their endpoints.
+///
+/// Reset connections are dropped right away, the others once they have been
+/// idle for longer than the timeout of their state.
+///
+/// Time never advances on its own, the caller passes the current time to
+/// every call so any clock can drive the timeouts.
+#[derive(Debug)]
+pub struct Tracker {
+	limits:      Limits,
+	connections: HashMap<Key, Entry>,
+	deadlines:   BTreeSet<(Instant, Key)>,
+}
+
+#[derive(Debug)]
+struct Entry {
+	deadline:   Instant,
+	connection: Connection,
+}
+
+impl Default for Tracker {
+	fn default() -> Self {
+		Tracker::new(Default::default())
+	}
+}
+
+impl Tracker {
+	/// Create an empty tracker with the given limits.
+	pub fn new(limits: Limits) -> Tracker {
+		Tracker {
+			limits:      limits,
+			connections: HashMap::new(),
+			deadlines:   BTreeSet::new(),
+		}
+	}
+
+	/// The resource limits.
+	pub fn limits(&self) -> &Limits {
+		&self.limits
+	}
+
+	/// Number of tracked connections.
+	pub fn len(&self) -> usize {
+		self.connections.len()
+	}
+
+	/// Check if no connection is tracked.
+	pub fn is_empty(&self) -> bool {
+		self.connections.is_empty()
+	}
+
+	/// Get a tracked connection, keyed by the endpoints of its first segment.
+	pub fn get(&self, key: &Key) -> Option<&Connection> {
+		self.connections.get(key).map(|entry| &entry.connection)
+	}
+
+	/// Stop tracking a connection.
+	pub fn remove(&mut self, key: &Key) -> Option<Connection> {
+		let entry = self.connections.remove(key)?;
+		self.deadlines.remove(&(entry.deadline, *key));
+
+		Some(entry.connection)
+	}
+
+	/// Track a segment, returning the direction it belongs to and the new
+	/// state of its connection.
+	///
+	/// # Errors
+	///
+	/// Only a SYN can start tracking a new connection, any other segment for
+	/// an unknown connection returns `Error::InvalidPacket`, while a SYN
+	/// exceeding the connection limit returns `Error::SmallBuffer`.
+	pub fn track<I: AsRef<[u8]>, B: AsRef<[u8]>>(&mut self, ip: &ip::Packet<I>, packet: &Packet<B>, now: Instant) -> Result<(Direction, State)> {
+		while self.pop(now).is_some() { }
+
+		let key = Key::new(ip, packet);
+
+		let (key, direction) = if self.connections.contains_key(&key) {
+			(key, Direction::Original)
+		}
+		else if self.connections.contains_key(&key.reverse()) {
+			(key.reverse(), Direction::Reply)
+		}
+		else {
+			let mut connection = Connection::new();
+			let state          = connection.update(Direction::Original, packet)?;
+
+			if self.connections.len() >= self.limits.connections {
+				Err(Error::SmallBuffer)?
+			}
+
+			let deadline = now + self.limits.timeout(state);
+			self.deadlines.insert((deadline, key));
+			self.connections.insert(key, Entry {
+				deadline:   deadline,
+				connection: connection,
+			});
+
+			return Ok((Direction::Original, state));
+		};
+
+		let entry = self.connections.get_mut(&key).unwrap();
+		let state = entry.connection.update(direction, packet)?;
+
+		if state == State::Closed {
+			self.remove(&key);
+		}
+		else {
+			self.deadlines.remove(&(entry.deadline, key));
+			entry.deadline = now + self.limits.timeout(state);
+			self.deadlines.insert((entry.deadline, key));
+		}
+
+		Ok((direction, state))
+	}
+
+	/// Drop the connections that have been idle for too long, returning their
+	/// keys.
+	pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+		std::iter::from_fn(|| self.pop(now)).collect()
+	}
+
+	/// Drop the connection with the earliest deadline if it passed.
+	fn pop(&mut self, now: Instant) -> Option<Key> {
+		let &(deadline, key) = self.deadlines.iter().next()?;
+
+		if deadline > now {
+			return None;
+		}
+
+		self.deadlines.remove(&(deadline, key));
+		self.connections.remove(&key);
+
+		Some(key)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::tcp;
+	use crate::tcp::flag::*;
+	use crate::tcp::Direction;
+	use std::time::{Duration, Instant};
+	use crate::tcp::conntrack::{Tracker, Limits, State};
+
+	fn segment(client: bool, sequence: u32, acknowledgment: u32, flags: tcp::Flags, window: u16, scale: Option<u8>, payload: &[u8]) -> Vec<u8> {
+		let (source, destination) = if client {
+			(("10.0.0.1", 1337), ("10.0.0.2", 80))
+		}
+		else {
+			(("10.0.0.2", 80), ("10.0.0.1", 1337))
+		};
+
+		let mut builder = ip::v4::Builder::default()
+			.source(source.0.parse().unwrap()).unwrap()
+			.destination(destination.0.parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(source.1).unwrap()
+				.destination(destination.1).unwrap()
+				.sequence(sequence).unwrap()
+				.acknowledgment(acknowledgment).unwrap()
+				.flags(flags).unwrap()
+				.window(window).unwrap();
+
+		if let Some(scale) = scale {
+			builder = builder.window_scale(scale).unwrap();
+		}
+
+		builder.payload(payload).unwrap().build().unwrap()
+	}
+
+	fn track(tracker: &mut Tracker, buffer: Vec<u8>) -> crate::error::Result<(Direction, State)> {
+		track_at(tracker, buffer, Instant::now())
+	}
+
+	fn track_at(tracker: &mut Tracker, buffer: Vec<u8>, now: Instant) -> crate::error::Result<(Direction, State)> {
+		let ip  = ip::Packet::new(&buffer[..]).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+
+		tracker.track(&ip, &tcp, now)
+	}
+
+	#[test]
+	fn lifecycle() {
+		let mut tracker = Tracker::default();
+
+		assert!(track(&mut tracker, segment(true, 100, 0, ACK, 1000, None, b"")).is_err());
+		assert!(tracker.is_empty());
+
+		assert_eq!(track(&mut tracker, segment(true, 100, 0, SYN, 65535, Some(7), b"")).unwrap(),
+			(Direction::Original, State::SynSent));
+		assert_eq!(track(&mut tracker, segment(false, 5000, 101, SYN | ACK, 1000, Some(2), b"")).unwrap(),
+			(Direction::Reply, State::SynReceived));
+		assert_eq!(track(&mut tracker, segment(true, 101, 5001, ACK, 512, None, b"")).unwrap(),
+			(Direction::Original, State::Established));
+
+		// The window of the server is scaled to 4000 bytes.
+		assert_eq!(track(&mut tracker, segment(false, 5001, 101, ACK, 1000, None, b"")).unwrap().1,
+			State::Established);
+		assert_eq!(track(&mut tracker, segment(true, 3101, 5001, ACK, 512, None, b"hello")).unwrap().1,
+			State::Established);
+		assert!(track(&mut tracker, segment(true, 5101, 5001, ACK, 512, None, b"hello")).is_err());
+		assert!(track(&mut tracker, segment(false, 5001, 9000, ACK, 1000, None, b"")).is_err());
+
+		assert_eq!(track(&mut tracker, segment(true, 3106, 5001, ACK | FIN, 512, None, b"")).unwrap().1,
+			State::FinWait);
+		assert_eq!(track(&mut tracker, segment(false, 5001, 3107, ACK, 1000, None, b"")).unwrap().1,
+			State::CloseWait);
+		assert_eq!(track(&mut tracker, segment(false, 5001, 3107, ACK | FIN, 1000, None, b"")).unwrap().1,
+			State::LastAck);
+		assert_eq!(track(&mut tracker, segment(true, 3107, 5002, ACK, 512, None, b"")).unwrap().1,
+			State::TimeWait);
+
+		assert_eq!(tracker.len(), 1);
+	}
+
+	#[test]
+	fn refused() {
+		let mut tracker = Tracker::default();
+
+		assert_eq!(track(&mut tracker, segment(true, 100, 0, SYN, 65535, None, b"")).unwrap().1,
+			State::SynSent);
+		assert!(track(&mut tracker, segment(false, 0, 42, RST | ACK, 0, None, b"")).is_err());
+		assert_eq!(track(&mut tracker, segment(false, 0, 101, RST | ACK, 0, None, b"")).unwrap(),
+			(Direction::Reply, State::Closed));
+		assert!(tracker.is_empty());
+		assert!(track(&mut tracker, segment(true, 101, 0, ACK, 1000, None, b"")).is_err());
+
+		// The connection can be opened again.
+		assert_eq!(track(&mut tracker, segment(true, 200, 0, SYN, 65535, None, b"")).unwrap().1,
+			State::SynSent);
+	}
+
+	fn establish(tracker: &mut Tracker, now: Instant) {
+		assert_eq!(track_at(tracker, segment(true, 100, 0, SYN, 65535, None, b""), now).unwrap().1,
+			State::SynSent);
+		assert_eq!(track_at(tracker, segment(false, 5000, 101, SYN | ACK, 1000, None, b""), now).unwrap().1,
+			State::SynReceived);
+		assert_eq!(track_at(tracker, segment(true, 101, 5001, ACK, 512, None, b""), now).unwrap().1,
+			State::Established);
+	}
+
+	#[test]
+	fn handshake_timeout() {
+		let mut tracker = Tracker::default();
+		let now         = Instant::now();
+
+		assert_eq!(track_at(&mut tracker, segment(true, 100, 0, SYN, 65535, None, b""), now).unwrap().1,
+			State::SynSent);
+		assert_eq!(track_at(&mut tracker, segment(false, 5000, 101, SYN | ACK, 1000, None, b""), now + Duration::from_secs(50)).unwrap().1,
+			State::SynReceived);
+
+		// The timeout counts from the last segment seen.
+		assert!(tracker.expire(now + Duration::from_secs(100)).is_empty());
+		assert_eq!(tracker.expire(now + Duration::from_secs(110)).len(), 1);
+		assert!(tracker.is_empty());
+	}
+
+	#[test]
+	fn established_timeout() {
+		let mut tracker = Tracker::default();
+		let now         = Instant::now();
+
+		establish(&mut tracker, now);
+		assert!(tracker.expire(now + Duration::from_secs(2 * 60 * 60)).is_empty());
+		assert_eq!(tracker.expire(now + Duration::from_secs(2 * 60 * 60 + 4 * 60)).len(), 1);
+		assert!(tracker.is_empty());
+	}
+
+	#[test]
+	fn closing_timeout() {
+		let mut tracker = Tracker::default();
+		let now         = Instant::now();
+
+		establish(&mut tracker, now);
+		assert_eq!(track_at(&mut tracker, segment(true, 101, 5001, ACK | FIN, 512, None, b""), now).unwrap().1,
+			State::FinWait);
+		assert!(tracker.expire(now + Duration::from_secs(119)).is_empty());
+		assert_eq!(tracker.expire(now + Duration::from_secs(120)).len(), 1);
+	}
+
+	#[test]
+	fn time_wait_timeout() {
+		let mut tracker = Tracker::default();
+		let now         = Instant::now();
+
+		establish(&mut tracker, now);
+		assert_eq!(track_at(&mut tracker, segment(true, 101, 5001, ACK | FIN, 512, None, b""), now).unwrap().1,
+			State::FinWait);
+		assert_eq!(track_at(&mut tracker, segment(false, 5001, 102, ACK | FIN, 1000, None, b""), now).unwrap().1,
+			State::LastAck);
+		assert_eq!(track_at(&mut tracker, segment(true, 102, 5002, ACK, 512, None, b""), now).unwrap().1,
+			State::TimeWait);
+		assert!(tracker.expire(now + Duration::from_secs(239)).is_empty());
+		assert_eq!(tracker.expire(now + Duration::from_secs(240)).len(), 1);
+	}
+
+	#[test]
+	fn limit() {
+		let mut tracker = Tracker::new(Limits { connections: 1, .. Default::default() });
+		let now         = Instant::now();
+
+		assert!(track_at(&mut tracker, segment(true, 100, 0, SYN, 65535, None, b""), now).is_ok());
+
+		// Another source port makes another connection.
+		let mut buffer = segment(true, 100, 0, SYN, 65535, None, b"");
+		buffer[21] += 1;
+
+		assert!(matches!(track_at(&mut tracker, buffer.clone(), now),
+			Err(crate::error::Error::SmallBuffer)));
+		assert_eq!(tracker.len(), 1);
+
+		// Half-open connections don't hold on to their slot for long.
+		assert_eq!(track_at(&mut tracker, buffer, now + Duration::from_secs(60)).unwrap().1,
+			State::SynSent);
+		assert_eq!(tracker.len(), 1);
+	}
+
+	#[test]
+	fn malformed() {
+		let mut buffer = segment(true, 100, 0, SYN, 65535, None, b"");
+		buffer.extend_from_slice(&[0x01, 0x01, 0x01, 0x02]);
+		buffer[3]  += 4;
+		buffer[32] += 0x10;
+
+		let mut tracker = Tracker::default();
+		assert!(track(&mut tracker, buffer).is_err());
+		assert!(tracker.is_empty());
+	}
+
+	#[test]
+	fn short_offset() {
+		let mut buffer = segment(true, 100, 0, SYN, 65535, None, b"");
+		buffer[32] = 0x00;
+
+		let ip  = ip::Packet::new(&buffer[..]).unwrap();
+		let tcp = tcp::Packet::unchecked(ip.payload());
+		assert!(tcp::Packet::new(ip.payload()).is_err());
+
+		let mut tracker = Tracker::default();
+		assert!(tracker.track(&ip, &tcp, Instant::now()).is_err());
+		assert!(tracker.is_empty());
+	}
+}
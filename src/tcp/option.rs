@@ -13,6 +13,8 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
 
 use crate::error::*;
 use crate::size;
@@ -66,6 +68,52 @@ pub enum Number {
 	///
 	Timestamp,
 
+	///
+	Md5Signature,
+
+	///
+	Authentication,
+
+	///
+	Multipath,
+
+	///
+	FastOpen,
+
+	///
+	Unknown(u8),
+}
+
+/// MPTCP option subtype.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum MultipathSubtype {
+	///
+	Capable,
+
+	///
+	Join,
+
+	///
+	DataSequenceSignal,
+
+	///
+	AddAddress,
+
+	///
+	RemoveAddress,
+
+	///
+	Priority,
+
+	///
+	Fail,
+
+	///
+	FastClose,
+
+	///
+	Reset,
+
 	///
 	Unknown(u8),
 }
@@ -91,10 +139,18 @@ impl<B: AsRef<[u8]>> Option<B> {
 			Err(Error::SmallBuffer)?
 		}
 
+		if option.buffer.as_ref().len() < 2 && option.buffer.as_ref()[0] > 1 {
+			Err(Error::SmallBuffer)?
+		}
+
 		if option.buffer.as_ref().len() < option.length() as usize {
 			Err(Error::SmallBuffer)?
 		}
 
+		if option.length() < 2 && option.buffer.as_ref()[0] > 1 {
+			Err(Error::InvalidPacket)?
+		}
+
 		Ok(option)
 	}
 }
@@ -171,19 +227,219 @@ impl<B: AsRef<[u8]>> Option<B> {
 	}
 }
 
+impl<B: AsRef<[u8]>> Option<B> {
+	/// The payload of the option, if it's the given one with the given payload
+	/// length.
+	fn expect(&self, number: Number, length: usize) -> Result<&[u8]> {
+		if self.number() != number {
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.payload().len() != length {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload())
+	}
+
+	/// Value of a Maximum Segment Size option.
+	pub fn mss(&self) -> Result<u16> {
+		Ok(self.expect(Number::MaximumSegmentSize, 2)?.read_u16::<BigEndian>()?)
+	}
+
+	/// Shift count of a Window Scale option.
+	pub fn window_scale(&self) -> Result<u8> {
+		Ok(self.expect(Number::WindowScale, 1)?[0])
+	}
+
+	/// Blocks of a Selective Acknowledgment option, as left and right edge
+	/// sequence numbers.
+	pub fn sack(&self) -> Result<impl Iterator<Item = (u32, u32)> + '_> {
+		if self.number() != Number::SelectiveAcknowledgment {
+			Err(Error::InvalidPacket)?
+		}
+
+		if self.payload().is_empty() || self.payload().len() % 8 != 0 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload().chunks_exact(8).map(|chunk|
+			((&chunk[..]).read_u32::<BigEndian>().unwrap(),
+			 (&chunk[4 ..]).read_u32::<BigEndian>().unwrap())))
+	}
+
+	/// Values of a Timestamp option, as the timestamp value and the echo
+	/// reply.
+	pub fn timestamp(&self) -> Result<(u32, u32)> {
+		let mut payload = self.expect(Number::Timestamp, 8)?;
+		Ok((payload.read_u32::<BigEndian>()?, payload.read_u32::<BigEndian>()?))
+	}
+
+	/// Digest of an MD5 Signature option.
+	pub fn md5_signature(&self) -> Result<&[u8]> {
+		self.expect(Number::Md5Signature, 16)
+	}
+
+	/// Parse a TCP Authentication Option.
+	pub fn authentication(&self) -> Result<Authentication<&[u8]>> {
+		if self.number() != Number::Authentication {
+			Err(Error::InvalidPacket)?
+		}
+
+		Authentication::new(self.payload())
+	}
+
+	/// Parse a Multipath TCP option.
+	pub fn multipath(&self) -> Result<Multipath<&[u8]>> {
+		if self.number() != Number::Multipath {
+			Err(Error::InvalidPacket)?
+		}
+
+		Multipath::new(self.payload())
+	}
+
+	/// Cookie of a Fast Open option, empty for a cookie request.
+	pub fn fast_open_cookie(&self) -> Result<&[u8]> {
+		if self.number() != Number::FastOpen {
+			Err(Error::InvalidPacket)?
+		}
+
+		// Cookies are between 4 and 16 octets, and always even.
+		match self.payload().len() {
+			0 | 4 ..= 16 if self.payload().len() % 2 == 0 =>
+				Ok(self.payload()),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Option<B> {
+	/// Value of a Maximum Segment Size option.
+	pub fn set_mss(&mut self, value: u16) -> Result<&mut Self> {
+		self.expect(Number::MaximumSegmentSize, 2)?;
+		Cursor::new(self.payload_mut())
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Shift count of a Window Scale option.
+	pub fn set_window_scale(&mut self, value: u8) -> Result<&mut Self> {
+		self.expect(Number::WindowScale, 1)?;
+		self.payload_mut()[0] = value;
+
+		Ok(self)
+	}
+
+	/// Values of a Timestamp option, as the timestamp value and the echo
+	/// reply.
+	pub fn set_timestamp(&mut self, value: u32, echo: u32) -> Result<&mut Self> {
+		self.expect(Number::Timestamp, 8)?;
+
+		let mut payload = Cursor::new(self.payload_mut());
+		payload.write_u32::<BigEndian>(value)?;
+		payload.write_u32::<BigEndian>(echo)?;
+
+		Ok(self)
+	}
+}
+
+/// TCP Authentication Option parser, as defined in RFC 5925.
+pub struct Authentication<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Authentication<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("tcp::option::Authentication")
+			.field("key_id", &self.key_id())
+			.field("next_key_id", &self.next_key_id())
+			.field("mac", &self.mac())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+	/// Parse a TCP Authentication Option payload.
+	pub fn new(buffer: B) -> Result<Authentication<B>> {
+		if buffer.as_ref().len() < 2 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Authentication { buffer })
+	}
+
+	/// Identifier of the key used for the MAC.
+	pub fn key_id(&self) -> u8 {
+		self.buffer.as_ref()[0]
+	}
+
+	/// Identifier of the key the sender is ready to receive.
+	pub fn next_key_id(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Message authentication code.
+	pub fn mac(&self) -> &[u8] {
+		&self.buffer.as_ref()[2 ..]
+	}
+}
+
+/// Multipath TCP option parser, as defined in RFC 8684.
+pub struct Multipath<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Multipath<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("tcp::option::Multipath")
+			.field("subtype", &self.subtype())
+			.field("data", &self.data())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Multipath<B> {
+	/// Parse a Multipath TCP option payload.
+	pub fn new(buffer: B) -> Result<Multipath<B>> {
+		if buffer.as_ref().is_empty() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(Multipath { buffer })
+	}
+
+	/// Option subtype.
+	pub fn subtype(&self) -> MultipathSubtype {
+		(self.buffer.as_ref()[0] >> 4).into()
+	}
+
+	/// The payload following the subtype, starting with the subtype specific
+	/// lower 4 bits.
+	pub fn data(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
 impl From<u8> for Number {
 	fn from(value: u8) -> Self {
 		use self::Number::*;
 
 		match value {
-			0 => End,
-			1 => NoOperation,
-			2 => MaximumSegmentSize,
-			3 => WindowScale,
-			4 => SelectiveAcknowledgmentPermitted,
-			5 => SelectiveAcknowledgment,
-			8 => Timestamp,
-			n => Unknown(n),
+			0  => End,
+			1  => NoOperation,
+			2  => MaximumSegmentSize,
+			3  => WindowScale,
+			4  => SelectiveAcknowledgmentPermitted,
+			5  => SelectiveAcknowledgment,
+			8  => Timestamp,
+			19 => Md5Signature,
+			29 => Authentication,
+			30 => Multipath,
+			34 => FastOpen,
+			n  => Unknown(n),
 		}
 	}
 }
@@ -200,7 +456,138 @@ impl Into<u8> for Number {
 			SelectiveAcknowledgmentPermitted => 4,
 			SelectiveAcknowledgment          => 5,
 			Timestamp                        => 8,
+			Md5Signature                     => 19,
+			Authentication                   => 29,
+			Multipath                        => 30,
+			FastOpen                         => 34,
 			Unknown(n)                       => n,
 		}
 	}
 }
+
+impl From<u8> for MultipathSubtype {
+	fn from(value: u8) -> Self {
+		use self::MultipathSubtype::*;
+
+		match value {
+			0 => Capable,
+			1 => Join,
+			2 => DataSequenceSignal,
+			3 => AddAddress,
+			4 => RemoveAddress,
+			5 => Priority,
+			6 => Fail,
+			7 => FastClose,
+			8 => Reset,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl Into<u8> for MultipathSubtype {
+	fn into(self) -> u8 {
+		use self::MultipathSubtype::*;
+
+		match self {
+			Capable            => 0,
+			Join               => 1,
+			DataSequenceSignal => 2,
+			AddAddress         => 3,
+			RemoveAddress      => 4,
+			Priority           => 5,
+			Fail               => 6,
+			FastClose          => 7,
+			Reset              => 8,
+			Unknown(n)         => n,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::tcp;
+	use crate::tcp::option::{Option, Number, MultipathSubtype};
+
+	#[test]
+	fn syn() {
+		let raw = [
+			0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8,
+			0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a,
+			0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04,
+			0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00,
+			0x01, 0x03, 0x03, 0x07,
+		];
+
+		let tcp     = tcp::Packet::new(&raw[20 ..]).unwrap();
+		let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 5);
+
+		assert_eq!(options[0].mss().unwrap(), 1460);
+		assert!(options[0].window_scale().is_err());
+		assert_eq!(options[1].number(), Number::SelectiveAcknowledgmentPermitted);
+		assert_eq!(options[2].timestamp().unwrap(), (0x592b2997, 0));
+		assert_eq!(options[3].number(), Number::NoOperation);
+		assert_eq!(options[4].window_scale().unwrap(), 7);
+	}
+
+	#[test]
+	fn sack() {
+		let raw = [0x05, 0x12,
+			0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+			0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x04];
+
+		let option = Option::new(&raw[..]).unwrap();
+		assert_eq!(option.sack().unwrap().collect::<Vec<_>>(), vec![(1, 2), (3, 4)]);
+		assert!(option.mss().is_err());
+
+		assert!(Option::new(&[0x05, 0x06, 0x00, 0x00, 0x00, 0x01][..]).unwrap().sack().is_err());
+	}
+
+	#[test]
+	fn kinds() {
+		let option = Option::new(&[0x22, 0x0a, 1, 2, 3, 4, 5, 6, 7, 8][..]).unwrap();
+		assert_eq!(option.number(), Number::FastOpen);
+		assert_eq!(option.fast_open_cookie().unwrap(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+		assert!(Option::new(&[0x22, 0x02][..]).unwrap().fast_open_cookie().unwrap().is_empty());
+		assert!(Option::new(&[0x22, 0x05, 1, 2, 3][..]).unwrap().fast_open_cookie().is_err());
+
+		let option = Option::new(&[0x13, 0x12, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+			0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa][..]).unwrap();
+		assert_eq!(option.number(), Number::Md5Signature);
+		assert_eq!(option.md5_signature().unwrap(), &[0xaa; 16]);
+
+		let option = Option::new(&[0x1d, 0x08, 0x01, 0x02, 0xde, 0xad, 0xbe, 0xef][..]).unwrap();
+		let ao     = option.authentication().unwrap();
+		assert_eq!(ao.key_id(), 1);
+		assert_eq!(ao.next_key_id(), 2);
+		assert_eq!(ao.mac(), &[0xde, 0xad, 0xbe, 0xef]);
+
+		let option = Option::new(&[0x1e, 0x04, 0x01, 0x81][..]).unwrap();
+		let mptcp  = option.multipath().unwrap();
+		assert_eq!(mptcp.subtype(), MultipathSubtype::Capable);
+		assert_eq!(mptcp.data(), &[0x01, 0x81]);
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(Option::new(&[0x02, 0x00][..]).is_err());
+		assert!(Option::new(&[0x02, 0x04, 0x05][..]).is_err());
+		assert!(Option::new(&[0x02, 0x03, 0x05][..]).unwrap().mss().is_err());
+		assert!(Option::new(&[0x02][..]).is_err());
+		assert!(Option::new(&[0x01][..]).is_ok());
+	}
+
+	#[test]
+	fn truncated() {
+		let raw = [
+			0x00u8, 0x50, 0x00, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x60, 0x02,
+			0x72, 0x10, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x02,
+		];
+
+		let tcp     = tcp::Packet::new(&raw[..]).unwrap();
+		let options = tcp.options().collect::<Vec<_>>();
+		assert_eq!(options.len(), 4);
+		assert!(options[.. 3].iter().all(|o| o.is_ok()));
+		assert!(options[3].is_err());
+	}
+}
@@ -74,6 +74,10 @@ impl<B: AsRef<[u8]>> Packet<B> {
             Err(Error::SmallBuffer)?
         }
 
+        if packet.offset() < 5 {
+            Err(Error::InvalidPacket)?
+        }
+
         if packet.buffer.as_ref().len() < packet.offset() as usize * 4 {
             Err(Error::SmallBuffer)?
         }
@@ -287,6 +291,84 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
         let checksum = checksum(ip, self.buffer.as_ref());
         self.set_checksum(checksum)
     }
+
+    /// TCP options for the packet, which can be rewritten in place.
+    ///
+    /// # Note
+    ///
+    /// The checksum is not updated, use `update_checksum` after changing
+    /// any option.
+    pub fn options_mut(&mut self) -> OptionIterMut<'_> {
+        let offset = self.offset() as usize * 4;
+
+        OptionIterMut {
+            buffer: &mut self.buffer.as_mut()[20..offset],
+        }
+    }
+
+    /// Lower the Maximum Segment Size option to the given value if it's
+    /// above it, returning whether the packet was changed.
+    ///
+    /// The checksum is updated incrementally as per RFC 1624, so it's only
+    /// correct if it was before.
+    pub fn clamp_mss(&mut self, max: u16) -> Result<bool> {
+        use crate::size::Size;
+
+        let mut position = 20;
+        let mut found = None;
+
+        for option in self.options() {
+            let option = option?;
+
+            if option.number() == option::Number::MaximumSegmentSize {
+                if option.mss()? > max {
+                    found = Some(position + 2);
+                }
+
+                break;
+            }
+
+            position += option.size();
+        }
+
+        let position = match found {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+
+        let mut checksum = self.checksum();
+        let buffer = self.buffer.as_mut();
+
+        // The value may not be aligned to the 16-bit words the checksum is
+        // made of, so adjust every word it touches.
+        let start = position & !1;
+        let end = (position + 3) & !1;
+
+        let old = buffer[start..end].to_vec();
+        Cursor::new(&mut buffer[position..]).write_u16::<BigEndian>(max)?;
+
+        for (old, new) in old.chunks(2).zip(buffer[start..end].chunks(2)) {
+            checksum = adjust(
+                checksum,
+                (&old[..]).read_u16::<BigEndian>()?,
+                (&new[..]).read_u16::<BigEndian>()?,
+            );
+        }
+
+        self.set_checksum(checksum)?;
+
+        Ok(true)
+    }
+}
+
+/// Incrementally update a checksum for a 16-bit word changing value, as
+/// defined in RFC 1624.
+fn adjust(checksum: u16, old: u16, new: u16) -> u16 {
+    let mut sum = u32::from(!checksum) + u32::from(!old) + u32::from(new);
+    sum = (sum & 0xffff) + (sum >> 16);
+    sum = (sum & 0xffff) + (sum >> 16);
+
+    !(sum as u16)
 }
 
 /// Checked wrapper for UDP packets.
@@ -386,11 +468,49 @@ impl<'a> Iterator for OptionIter<'a> {
                 Some(Ok(option))
             }
 
-            Err(error) => Some(Err(error)),
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
         }
     }
 }
 
+/// Mutable iterator over TCP packet options.
+pub struct OptionIterMut<'a> {
+    buffer: &'a mut [u8],
+}
+
+impl<'a> Iterator for OptionIterMut<'a> {
+    type Item = Result<option::Option<&'a mut [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use crate::size::Size;
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let buffer = std::mem::take(&mut self.buffer);
+        let size = match option::Option::new(&buffer[..]) {
+            Ok(option) => {
+                if option.number() == option::Number::End {
+                    return None;
+                }
+
+                option.size()
+            }
+
+            Err(error) => return Some(Err(error)),
+        };
+
+        let (current, rest) = buffer.split_at_mut(size);
+        self.buffer = rest;
+
+        Some(option::Option::new(current))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::ip;
@@ -465,4 +585,121 @@ mod test {
         assert_eq!(tcp.destination(), 9001);
         assert!(tcp.is_valid(&ip));
     }
+
+    #[test]
+    fn options_mut() {
+        let mut raw = [
+            0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8,
+            0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a,
+            0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04,
+            0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00,
+            0x01, 0x03, 0x03, 0x07,
+        ];
+
+        let mut ip = ip::v4::Packet::new(&mut raw[..]).unwrap();
+        let (ip, tcp) = ip.split_mut();
+        let ip = ip::Packet::from(ip::v4::Packet::unchecked(ip));
+        let mut tcp = tcp::Packet::new(tcp).unwrap();
+
+        for option in tcp.options_mut() {
+            let mut option = option.unwrap();
+
+            match option.number() {
+                tcp::option::Number::Timestamp => {
+                    option.set_timestamp(1, 2).unwrap();
+                }
+
+                tcp::option::Number::WindowScale => {
+                    option.set_window_scale(9).unwrap();
+                }
+
+                _ => (),
+            }
+        }
+
+        assert!(!tcp.is_valid(&ip));
+        tcp.update_checksum(&ip).unwrap();
+
+        let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options[2].timestamp().unwrap(), (1, 2));
+        assert_eq!(options[4].window_scale().unwrap(), 9);
+    }
+
+    #[test]
+    fn clamp_mss() {
+        let mut raw = [
+            0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8,
+            0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a,
+            0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04,
+            0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00,
+            0x01, 0x03, 0x03, 0x07,
+        ];
+
+        let mut ip = ip::v4::Packet::new(&mut raw[..]).unwrap();
+        let (ip, tcp) = ip.split_mut();
+        let ip = ip::Packet::from(ip::v4::Packet::unchecked(ip));
+        let mut tcp = tcp::Packet::new(tcp).unwrap();
+
+        assert!(!tcp.clamp_mss(1500).unwrap());
+        assert!(tcp.clamp_mss(1452).unwrap());
+        assert_eq!(tcp.options().next().unwrap().unwrap().mss().unwrap(), 1452);
+        assert!(tcp.is_valid(&ip));
+    }
+
+    #[test]
+    fn clamp_mss_unaligned() {
+        use crate::builder::Builder;
+
+        let mut packet = ip::v4::Builder::default()
+            .source("192.168.0.79".parse().unwrap())
+            .unwrap()
+            .destination("10.0.0.1".parse().unwrap())
+            .unwrap()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::SYN)
+            .unwrap()
+            .window_scale(7)
+            .unwrap()
+            .mss(0xffff)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut ip = ip::v4::Packet::new(&mut packet[..]).unwrap();
+        let (ip, tcp) = ip.split_mut();
+        let ip = ip::Packet::from(ip::v4::Packet::unchecked(ip));
+        let mut tcp = tcp::Packet::new(tcp).unwrap();
+
+        assert!(tcp.is_valid(&ip));
+        assert!(tcp.clamp_mss(1400).unwrap());
+        assert_eq!(tcp.options().nth(1).unwrap().unwrap().mss().unwrap(), 1400);
+        assert!(tcp.is_valid(&ip));
+    }
+
+    #[test]
+    fn clamp_mss_truncated() {
+        let mut raw = [
+            0x00u8, 0x50, 0x00, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x60, 0x02,
+            0x72, 0x10, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x02,
+        ];
+
+        let mut tcp = tcp::Packet::new(&mut raw[..]).unwrap();
+        assert!(tcp.clamp_mss(1400).is_err());
+    }
+
+    #[test]
+    fn short_offset() {
+        let mut raw = [
+            0x00u8, 0x50, 0x00, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+            0x72, 0x10, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        assert!(tcp::Packet::new(&raw[..]).is_err());
+
+        raw[12] = 0x40;
+        assert!(tcp::Packet::new(&mut raw[..]).is_err());
+    }
 }
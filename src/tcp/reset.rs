@@ -0,0 +1,169 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use crate::error::*;
+use crate::buffer;
+use crate::builder::Builder as Build;
+use crate::packet::Packet as P;
+use crate::ip;
+use crate::tcp::{self, Packet, Flags, flag};
+
+/// Build a RST segment in response to the given segment, as described in
+/// RFC 9293 section 3.10.7.1.
+///
+/// The addresses and ports are swapped, if the offending segment carries an
+/// acknowledgment the reset takes its sequence number from it, otherwise the
+/// reset acknowledges everything the segment occupied in sequence space.
+///
+/// # Errors
+///
+/// A reset is never sent in response to a reset, which returns
+/// `Error::InvalidPacket`.
+pub fn reset_for<I: AsRef<[u8]>, B: AsRef<[u8]>>(ip: &ip::Packet<I>, packet: &Packet<B>) -> Result<Vec<u8>> {
+	if packet.flags().contains(flag::RST) {
+		Err(Error::InvalidPacket)?
+	}
+
+	match *ip {
+		ip::Packet::V4(ref ip) =>
+			reset(ip::v4::Builder::default()
+				.ttl(64)?
+				.source(ip.destination())?
+				.destination(ip.source())?
+				.tcp()?, packet),
+
+		ip::Packet::V6(ref ip) =>
+			reset(ip::v6::Builder::default()
+				.hop_limit(64)?
+				.source(ip.final_destination())?
+				.destination(ip.source())?
+				.tcp()?, packet),
+	}
+}
+
+fn reset<B: AsRef<[u8]>>(builder: tcp::Builder<buffer::Dynamic>, packet: &Packet<B>) -> Result<Vec<u8>> {
+	let flags   = packet.flags();
+	let builder = builder
+		.source(packet.destination())?
+		.destination(packet.source())?
+		.window(0)?;
+
+	if flags.contains(flag::ACK) {
+		builder
+			.sequence(packet.acknowledgment())?
+			.flags(flag::RST)?
+			.build()
+	}
+	else {
+		let mut length = packet.payload().len() as u32;
+
+		if flags.contains(flag::SYN) {
+			length += 1;
+		}
+
+		if flags.contains(flag::FIN) {
+			length += 1;
+		}
+
+		builder
+			.sequence(0)?
+			.acknowledgment(packet.sequence().wrapping_add(length))?
+			.flags(Flags::RST | Flags::ACK)?
+			.build()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::tcp;
+
+	#[test]
+	fn syn() {
+		let packet = ip::v4::Builder::default()
+			.ttl(64).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("10.0.0.1".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(80).unwrap()
+				.sequence(0xffff_ffff).unwrap()
+				.flags(tcp::flag::SYN).unwrap()
+				.build().unwrap();
+
+		let ip  = ip::Packet::new(&packet[..]).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+
+		let reset = tcp::reset_for(&ip, &tcp).unwrap();
+		let ip    = ip::Packet::new(&reset[..]).unwrap();
+		let tcp   = tcp::Packet::new(ip.payload()).unwrap();
+
+		if let ip::Packet::V4(ref packet) = ip {
+			assert_eq!(packet.source(), "10.0.0.1".parse::<std::net::Ipv4Addr>().unwrap());
+			assert_eq!(packet.destination(), "192.168.0.79".parse::<std::net::Ipv4Addr>().unwrap());
+		}
+		else {
+			unreachable!();
+		}
+
+		assert!(tcp.is_valid(&ip));
+		assert_eq!(tcp.source(), 80);
+		assert_eq!(tcp.destination(), 1337);
+		assert_eq!(tcp.flags(), tcp::flag::RST | tcp::flag::ACK);
+		assert_eq!(tcp.sequence(), 0);
+		assert_eq!(tcp.acknowledgment(), 0);
+		assert_eq!(tcp.window(), 0);
+
+		assert!(tcp::reset_for(&ip, &tcp).is_err());
+	}
+
+	#[test]
+	fn ack() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(80).unwrap()
+				.sequence(100).unwrap()
+				.acknowledgment(4242).unwrap()
+				.flags(tcp::flag::ACK | tcp::flag::PSH).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let ip  = ip::Packet::new(&packet[..]).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+
+		let reset = tcp::reset_for(&ip, &tcp).unwrap();
+		let ip    = ip::Packet::new(&reset[..]).unwrap();
+		let tcp   = tcp::Packet::new(ip.payload()).unwrap();
+
+		if let ip::Packet::V6(ref packet) = ip {
+			assert_eq!(packet.source(), "2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap());
+			assert_eq!(packet.destination(), "2001:db8::1".parse::<std::net::Ipv6Addr>().unwrap());
+		}
+		else {
+			unreachable!();
+		}
+
+		assert!(tcp.is_valid(&ip));
+		assert_eq!(tcp.source(), 80);
+		assert_eq!(tcp.destination(), 1337);
+		assert_eq!(tcp.flags(), tcp::flag::RST);
+		assert_eq!(tcp.sequence(), 4242);
+		assert!(tcp.payload().is_empty());
+	}
+}
@@ -0,0 +1,423 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+use std::collections::BTreeMap;
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::tcp::Packet;
+use crate::tcp::Flags;
+
+/// Direction of a segment within a connection.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Direction {
+	/// From the endpoint that opened the connection.
+	Original,
+
+	/// From the endpoint that accepted the connection.
+	Reply,
+}
+
+impl Direction {
+	/// The opposite direction.
+	pub fn reverse(self) -> Direction {
+		match self {
+			Direction::Original => Direction::Reply,
+			Direction::Reply    => Direction::Original,
+		}
+	}
+}
+
+/// Something that happened on one direction of the stream.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Event {
+	/// Contiguous data following what was delivered before.
+	Data(Direction, Vec<u8>),
+
+	/// Number of bytes that are missing and will never be delivered.
+	Gap(Direction, u64),
+
+	/// The sender closed its side, no more data follows.
+	Fin(Direction),
+
+	/// The sender reset the connection.
+	Reset(Direction),
+}
+
+/// TCP stream reassembler for a single connection.
+///
+/// Segments are accepted in any order and ordered by sequence number with
+/// wraparound, retransmitted data is dropped and overlapping data keeps
+/// the bytes received first.
+///
+/// A hole is waited on until the peer acknowledges data past it, the
+/// buffered data goes over the limit or the direction is flushed, at which
+/// point a gap is reported and the following data is delivered.
+#[derive(Debug)]
+pub struct Reassembler {
+	limit:    usize,
+	original: Stream,
+	reply:    Stream,
+}
+
+#[derive(Default, Debug)]
+struct Stream {
+	next:     Option<u32>,
+	offset:   u64,
+	pending:  BTreeMap<u64, Vec<u8>>,
+	buffered: usize,
+	fin:      Option<u64>,
+	closed:   bool,
+}
+
+impl Default for Reassembler {
+	fn default() -> Self {
+		Reassembler::new(256 * 1024)
+	}
+}
+
+impl Reassembler {
+	/// Create a reassembler buffering at most the given number of bytes per
+	/// direction.
+	pub fn new(limit: usize) -> Reassembler {
+		Reassembler {
+			limit:    limit,
+			original: Default::default(),
+			reply:    Default::default(),
+		}
+	}
+
+	fn stream(&mut self, direction: Direction) -> &mut Stream {
+		match direction {
+			Direction::Original => &mut self.original,
+			Direction::Reply    => &mut self.reply,
+		}
+	}
+
+	/// Number of bytes delivered, including gaps, in the given direction.
+	pub fn offset(&self, direction: Direction) -> u64 {
+		match direction {
+			Direction::Original => self.original.offset,
+			Direction::Reply    => self.reply.offset,
+		}
+	}
+
+	/// Number of bytes waiting for a hole to be filled in the given direction.
+	pub fn buffered(&self, direction: Direction) -> usize {
+		match direction {
+			Direction::Original => self.original.buffered,
+			Direction::Reply    => self.reply.buffered,
+		}
+	}
+
+	/// Insert a segment sent in the given direction, returning what can be
+	/// delivered after it.
+	pub fn insert<B: AsRef<[u8]>>(&mut self, direction: Direction, packet: &Packet<B>) -> Result<Vec<Event>> {
+		let flags      = packet.flags();
+		let limit      = self.limit;
+		let mut events = Vec::new();
+
+		if flags.contains(Flags::RST) {
+			events.push(Event::Reset(direction));
+			return Ok(events);
+		}
+
+		{
+			let stream = self.stream(direction);
+			let mut sequence = packet.sequence();
+
+			// The SYN takes a sequence number before the data.
+			if flags.contains(Flags::SYN) {
+				sequence = sequence.wrapping_add(1);
+
+				if stream.next.is_none() {
+					stream.next = Some(sequence);
+				}
+			}
+
+			stream.insert(direction, sequence, packet.payload(), flags.contains(Flags::FIN), limit, &mut events)?;
+		}
+
+		// Whatever the peer acknowledges it has received, so a hole before it
+		// will never be filled in the capture.
+		if flags.contains(Flags::ACK) {
+			self.stream(direction.reverse()).acknowledge(direction.reverse(), packet.acknowledgment(), &mut events);
+		}
+
+		Ok(events)
+	}
+
+	/// Deliver all the buffered data in the given direction, reporting the
+	/// holes as gaps.
+	pub fn flush(&mut self, direction: Direction) -> Vec<Event> {
+		let mut events = Vec::new();
+		let stream     = self.stream(direction);
+
+		while let Some(&start) = stream.pending.keys().next() {
+			stream.skip(direction, start, &mut events);
+		}
+
+		if let Some(fin) = stream.fin {
+			stream.skip(direction, fin, &mut events);
+		}
+
+		events
+	}
+}
+
+impl Stream {
+	/// Offset in the stream of the given sequence number.
+	fn position(&self, sequence: u32) -> Option<i64> {
+		let next = self.next?;
+		Some(self.offset as i64 + i64::from(sequence.wrapping_sub(next) as i32))
+	}
+
+	fn insert(&mut self, direction: Direction, sequence: u32, payload: &[u8], fin: bool, limit: usize, events: &mut Vec<Event>) -> Result<()> {
+		if self.closed {
+			return Ok(());
+		}
+
+		// Start from the first segment seen when the handshake was missed.
+		if self.next.is_none() {
+			self.next = Some(sequence);
+		}
+
+		let start = self.position(sequence).unwrap();
+		let end   = start + payload.len() as i64;
+
+		if self.fin.is_some_and(|fin| end > fin as i64) {
+			Err(Error::InvalidPacket)?
+		}
+
+		if fin {
+			let last = self.pending.iter().next_back().map(|(&from, data)| from + data.len() as u64);
+
+			if self.fin.is_some_and(|fin| fin as i64 != end) || end < self.offset as i64 || last.is_some_and(|last| last as i64 > end) {
+				Err(Error::InvalidPacket)?
+			}
+
+			self.fin = Some(end as u64);
+		}
+
+		// Drop whatever was already delivered, and only keep the bytes that
+		// aren't already buffered.
+		let mut cursor = start.max(self.offset as i64) as u64;
+		let mut pieces = Vec::new();
+
+		if end > cursor as i64 {
+			let end = end as u64;
+
+			for (&from, data) in self.pending.range(.. end) {
+				let to = from + data.len() as u64;
+
+				if to <= cursor {
+					continue;
+				}
+
+				if from > cursor {
+					pieces.push((cursor, from));
+				}
+
+				cursor = cursor.max(to);
+			}
+
+			if cursor < end {
+				pieces.push((cursor, end));
+			}
+		}
+
+		for (from, to) in pieces {
+			let data = &payload[(from as i64 - start) as usize .. (to as i64 - start) as usize];
+
+			self.buffered += data.len();
+			self.pending.insert(from, data.to_vec());
+		}
+
+		self.deliver(direction, events);
+
+		while self.buffered > limit {
+			let start = *self.pending.keys().next().unwrap();
+			self.skip(direction, start, events);
+		}
+
+		Ok(())
+	}
+
+	/// Deliver the data following what was already delivered.
+	fn deliver(&mut self, direction: Direction, events: &mut Vec<Event>) {
+		while let Some(data) = self.pending.remove(&self.offset) {
+			self.buffered -= data.len();
+			self.advance(data.len() as u64);
+
+			events.push(Event::Data(direction, data));
+		}
+
+		if !self.closed && self.fin == Some(self.offset) {
+			self.closed = true;
+			self.next   = self.next.map(|next| next.wrapping_add(1));
+
+			events.push(Event::Fin(direction));
+		}
+	}
+
+	/// Move past the given number of bytes.
+	fn advance(&mut self, length: u64) {
+		self.offset += length;
+		self.next    = self.next.map(|next| next.wrapping_add(length as u32));
+	}
+
+	/// Give up on the data before the given offset, reporting the holes.
+	fn skip(&mut self, direction: Direction, target: u64, events: &mut Vec<Event>) {
+		while self.offset < target && !self.closed {
+			let hole = match self.pending.keys().next() {
+				Some(&start) => start.min(target),
+				None         => target,
+			};
+
+			if hole > self.offset {
+				events.push(Event::Gap(direction, hole - self.offset));
+				self.advance(hole - self.offset);
+			}
+
+			self.deliver(direction, events);
+		}
+	}
+
+	/// Handle an acknowledgment from the peer.
+	fn acknowledge(&mut self, direction: Direction, acknowledgment: u32, events: &mut Vec<Event>) {
+		let target = match self.position(acknowledgment) {
+			Some(target) if target > self.offset as i64 =>
+				target as u64,
+
+			_ =>
+				return,
+		};
+
+		// The FIN takes a sequence number after the data.
+		let target = match self.fin {
+			Some(fin) => target.min(fin),
+			None      => target,
+		};
+
+		self.skip(direction, target, events);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::tcp;
+	use crate::tcp::stream::{Reassembler, Direction, Event};
+
+	fn segment(sequence: u32, acknowledgment: u32, flags: tcp::Flags, payload: &[u8]) -> Vec<u8> {
+		let packet = ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("10.0.0.2".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(80).unwrap()
+				.sequence(sequence).unwrap()
+				.acknowledgment(acknowledgment).unwrap()
+				.flags(flags).unwrap()
+				.payload(payload).unwrap()
+				.build().unwrap();
+
+		ip::v4::Packet::new(packet).unwrap().payload().to_vec()
+	}
+
+	fn insert(stream: &mut Reassembler, direction: Direction, sequence: u32, acknowledgment: u32, flags: tcp::Flags, payload: &[u8]) -> Vec<Event> {
+		let buffer = segment(sequence, acknowledgment, flags, payload);
+		stream.insert(direction, &tcp::Packet::new(&buffer[..]).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn handshake() {
+		use crate::tcp::flag::*;
+		use crate::tcp::stream::Direction::*;
+
+		let client = 0xffff_fff8u32;
+		let server = 0x1000u32;
+
+		let mut stream = Reassembler::default();
+		assert!(insert(&mut stream, Original, client, 0, SYN, b"").is_empty());
+		assert!(insert(&mut stream, Reply, server, client.wrapping_add(1), SYN | ACK, b"").is_empty());
+
+		// Crosses the sequence number wraparound.
+		assert_eq!(insert(&mut stream, Original, client.wrapping_add(1), server + 1, ACK | PSH, b"GET / HTTP/1.0\r\n\r\n"),
+			vec![Event::Data(Original, b"GET / HTTP/1.0\r\n\r\n".to_vec())]);
+
+		assert_eq!(insert(&mut stream, Reply, server + 1, client.wrapping_add(19), ACK | PSH | FIN, b"HTTP/1.0 200 OK\r\n\r\n"),
+			vec![Event::Data(Reply, b"HTTP/1.0 200 OK\r\n\r\n".to_vec()), Event::Fin(Reply)]);
+
+		assert_eq!(insert(&mut stream, Original, client.wrapping_add(19), server + 21, ACK | FIN, b""),
+			vec![Event::Fin(Original)]);
+
+		assert_eq!(stream.offset(Original), 18);
+		assert_eq!(stream.offset(Reply), 19);
+		assert_eq!(insert(&mut stream, Reply, server + 21, 0, RST, b""), vec![Event::Reset(Reply)]);
+	}
+
+	#[test]
+	fn out_of_order() {
+		use crate::tcp::flag::*;
+		use crate::tcp::stream::Direction::*;
+
+		let mut stream = Reassembler::default();
+		assert!(insert(&mut stream, Original, 99, 0, SYN, b"").is_empty());
+		assert!(insert(&mut stream, Original, 106, 0, ACK, b"world").is_empty());
+		assert!(insert(&mut stream, Original, 104, 0, ACK, b"o XXXX").is_empty());
+		assert_eq!(stream.buffered(Original), 7);
+
+		assert_eq!(insert(&mut stream, Original, 100, 0, ACK, b"hello"), vec![
+			Event::Data(Original, b"hell".to_vec()),
+			Event::Data(Original, b"o ".to_vec()),
+			Event::Data(Original, b"world".to_vec())]);
+
+		// Retransmissions are dropped.
+		assert!(insert(&mut stream, Original, 100, 0, ACK, b"hello").is_empty());
+		assert_eq!(insert(&mut stream, Original, 108, 0, ACK, b"rld!"),
+			vec![Event::Data(Original, b"!".to_vec())]);
+		assert_eq!(stream.buffered(Original), 0);
+	}
+
+	#[test]
+	fn gaps() {
+		use crate::tcp::flag::*;
+		use crate::tcp::stream::Direction::*;
+
+		let mut stream = Reassembler::default();
+		assert_eq!(insert(&mut stream, Original, 1000, 0, ACK, b"abc").len(), 1);
+		assert!(insert(&mut stream, Original, 1010, 0, ACK, b"xyz").is_empty());
+		assert!(insert(&mut stream, Original, 1020, 0, ACK, b"123").is_empty());
+
+		// The peer acknowledged data the capture never saw.
+		assert_eq!(insert(&mut stream, Reply, 5000, 1015, ACK, b""), vec![
+			Event::Gap(Original, 7),
+			Event::Data(Original, b"xyz".to_vec()),
+			Event::Gap(Original, 2)]);
+
+		assert_eq!(stream.flush(Original), vec![
+			Event::Gap(Original, 5),
+			Event::Data(Original, b"123".to_vec())]);
+
+		let mut stream = Reassembler::new(4);
+		assert_eq!(insert(&mut stream, Reply, 0, 0, ACK, b"a").len(), 1);
+		assert!(insert(&mut stream, Reply, 2, 0, ACK, b"bcd").is_empty());
+		assert_eq!(insert(&mut stream, Reply, 6, 0, ACK, b"ef"), vec![
+			Event::Gap(Reply, 1),
+			Event::Data(Reply, b"bcd".to_vec())]);
+		assert_eq!(stream.buffered(Reply), 2);
+	}
+}
@@ -56,9 +56,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 	fn build(mut self) -> Result<B::Inner> {
 		self.prepare();
 
-		let mut buffer = self.buffer.into_inner();
-		self.finalizer.finalize(buffer.as_mut())?;
-		Ok(buffer)
+		self.finalizer.build(self.buffer)
 	}
 }
 
@@ -174,4 +172,38 @@ mod test {
 		assert_eq!(udp.destination(), 9001);
 		assert!(udp.is_valid(&ip::Packet::from(&ip)));
 	}
+
+	#[test]
+	fn zero_checksum() {
+		let build = |payload: &[u8]| ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.payload(payload).unwrap()
+				.build().unwrap();
+
+		// Adding the complement of the checksum as payload makes the sum 0xffff.
+		let packet   = build(&[0, 0]);
+		let ip       = ip::v6::Packet::new(&packet[..]).unwrap();
+		let checksum = udp::Packet::new(ip.payload()).unwrap().checksum();
+
+		let mut packet = build(&[(checksum >> 8) as u8, checksum as u8]);
+
+		{
+			let ip  = ip::v6::Packet::new(&packet[..]).unwrap();
+			let udp = udp::Packet::new(ip.payload()).unwrap();
+			assert_eq!(udp.checksum(), 0xffff);
+			assert!(udp.is_valid(&ip::Packet::from(&ip)));
+		}
+
+		// A missing checksum is not allowed over IPv6.
+		packet[46] = 0;
+		packet[47] = 0;
+
+		let ip  = ip::v6::Packet::new(&packet[..]).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert!(!udp.is_valid(&ip::Packet::from(&ip)));
+	}
 }
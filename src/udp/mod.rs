@@ -42,8 +42,13 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 				.write_u16::<BigEndian>(buffer.len() as u16).unwrap();
 		}
 
-		ip::Packet::V6(ref _packet) => {
-			unimplemented!();
+		ip::Packet::V6(ref packet) => {
+			prefix[0 .. 16].copy_from_slice(&packet.source().octets());
+			prefix[16 .. 32].copy_from_slice(&packet.final_destination().octets());
+
+			Cursor::new(&mut prefix[32 ..])
+				.write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+			prefix[39] = Protocol::Udp.into();
 		}
 	};
 
@@ -78,9 +83,9 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 		}
 	}
 
-	if let Ok(value) = buffer.read_u8() {
+	if buffer.get_ref().len() % 2 == 1 {
 		// if we have a trailing byte, make a padded 16-bit value.
-		let value = (value as u16) << 8;
+		let value = u16::from(buffer.get_ref()[buffer.get_ref().len() - 1]) << 8;
 
 		result += u32::from(value);
 
@@ -89,7 +94,12 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 		}
 	}
 
-	!result as u16
+	// A computed checksum of zero is sent as all ones, since zero means no
+	// checksum was computed.
+	match !result as u16 {
+		0 => 0xffff,
+		checksum => checksum,
+	}
 }
 
 #[cfg(test)]
@@ -116,4 +126,24 @@ mod tests {
 
 		assert_eq!(checksum(&ip::Packet::V4(ip), ip.payload()), udp.checksum());
 	}
+
+	#[test]
+	fn test_checksum_v6() {
+		let raw = [
+			// IPv6
+			0x60, 0x00, 0x00, 0x00, 0x00, 0x0d, 0x11, 0x40, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00,
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x20, 0x01, 0x0d, 0xb8,
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+			// UDP
+			0x05, 0x39, 0x00, 0x35, 0x00, 0x0d, 0x5b, 0x1f,
+			// data
+			0x68, 0x65, 0x6c, 0x6c, 0x6f,
+		];
+
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(checksum(&ip::Packet::V6(ip), ip.payload()), 0x5b1f);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
 }
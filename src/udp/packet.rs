@@ -185,8 +185,17 @@ impl<B: AsRef<[u8]>> Packet<B> {
 	}
 
 	/// Verify the packet is valid by calculating the checksum.
+	///
+	/// A zero checksum means none was computed, which is only allowed over
+	/// IPv4.
 	pub fn is_valid<I: AsRef<[u8]>>(&self, ip: &ip::Packet<I>) -> bool {
-		checksum(ip, self.buffer.as_ref()) == self.checksum()
+		match (ip, self.checksum()) {
+			(ip::Packet::V4(_), 0) =>
+				true,
+
+			(_, value) =>
+				checksum(ip, self.buffer.as_ref()) == value,
+		}
 	}
 }
 